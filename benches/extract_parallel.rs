@@ -0,0 +1,54 @@
+//! Compares `Archive::extract_parallel` against `Archive::extract_serial`
+//! on an archive full of medium-sized files, where the win from spreading
+//! decode work across threads should be closest to linear.
+
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use rc_zip::prelude::*;
+
+/// Builds a throwaway archive of `count` entries of `size` random-ish bytes
+/// each, so the benchmark doesn't depend on a fixture checked into the repo.
+fn build_fixture(dir: &std::path::Path, count: usize, size: usize) -> std::path::PathBuf {
+    let zip_path = dir.join("fixture.zip");
+    let file = std::fs::File::create(&zip_path).unwrap();
+    let mut writer = rc_zip::ArchiveWriter::new(file);
+    let payload = vec![0x5au8; size];
+    for i in 0..count {
+        let options = rc_zip::EntryOptions::new(rc_zip::Mode(0o644), rc_zip::Method::Deflate);
+        let mut entry = writer.start_entry(&format!("file-{:04}.bin", i), options).unwrap();
+        entry.write_all(&payload).unwrap();
+        entry.finish().unwrap();
+    }
+    writer.finish().unwrap();
+    zip_path
+}
+
+fn bench_extract(c: &mut Criterion) {
+    let tmp = tempfile::tempdir().unwrap();
+    let zip_path = build_fixture(tmp.path(), 200, 64 * 1024);
+    let file = std::fs::File::open(&zip_path).unwrap();
+    let archive = file.read_zip().unwrap();
+
+    let mut group = c.benchmark_group("extract");
+    for threads in [1, 2, 4, 8] {
+        group.bench_with_input(BenchmarkId::new("parallel", threads), &threads, |b, &threads| {
+            b.iter(|| {
+                let dest = tempfile::tempdir().unwrap();
+                archive.extract_parallel(dest.path(), threads, |offset| {
+                    positioned_io::Cursor::new_pos(&file, offset)
+                });
+            });
+        });
+    }
+    group.bench_function("serial", |b| {
+        b.iter(|| {
+            let dest = tempfile::tempdir().unwrap();
+            archive.extract_serial(dest.path(), |offset| {
+                positioned_io::Cursor::new_pos(&file, offset)
+            });
+        });
+    });
+    group.finish();
+}
+
+criterion_group!(benches, bench_extract);
+criterion_main!(benches);
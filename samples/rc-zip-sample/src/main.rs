@@ -3,7 +3,7 @@ use humansize::{file_size_opts::BINARY, FileSize};
 use rc_zip::prelude::*;
 use std::fmt;
 use std::fs::File;
-use std::io::Read;
+use std::io::{Read, Seek, Write};
 
 struct Optional<T>(Option<T>);
 
@@ -74,6 +74,29 @@ fn main() {
                         .help("ZIP file to extract")
                         .required(true)
                         .index(1),
+                )
+                .arg(
+                    Arg::with_name("password")
+                        .help("Password for encrypted entries")
+                        .long("--password")
+                        .short("-p")
+                        .takes_value(true),
+                )
+                .arg(
+                    Arg::with_name("dest")
+                        .help("Directory to extract into")
+                        .long("--dest")
+                        .short("-d")
+                        .takes_value(true)
+                        .default_value("."),
+                )
+                .arg(
+                    Arg::with_name("jobs")
+                        .help("Number of worker threads to extract with")
+                        .long("--jobs")
+                        .short("-j")
+                        .takes_value(true)
+                        .default_value("4"),
                 ),
         )
         .subcommand(
@@ -91,7 +114,8 @@ fn main() {
                         .help("Path of the zip file to crate")
                         .required(true)
                         .long("--output")
-                        .short("-o"),
+                        .short("-o")
+                        .takes_value(true),
                 ),
         )
         .get_matches();
@@ -148,10 +172,36 @@ fn do_main(matches: ArgMatches) -> Result<(), Box<dyn std::error::Error>> {
         );
     }
 
+    fn list_streaming(mut src: impl Read) -> Result<(), Box<dyn std::error::Error>> {
+        let mut reader = rc_zip::StreamingArchiveReader::new(&mut src);
+        while let Some(mut entry) = reader.next_entry()? {
+            let mut discarded = Vec::new();
+            std::io::copy(&mut entry, &mut discarded)?;
+            println!(
+                "{name}\t{method:?}\t{size}",
+                name = entry.name,
+                method = entry.method,
+                size = entry.uncompressed_size.file_size(BINARY).unwrap(),
+            );
+        }
+        Ok(())
+    }
+
     match matches.subcommand() {
         ("info", Some(matches)) => {
-            let reader = File::open(matches.value_of("file").unwrap())?.read_zip()?;
-            info(&reader);
+            let path = matches.value_of("file").unwrap();
+            if path == "-" {
+                // The central directory lives at the end of the file, which
+                // a pipe can't seek back to; fall back to walking local
+                // file headers from the front instead.
+                list_streaming(std::io::stdin())?;
+            } else {
+                let reader = File::open(path)?.read_zip()?;
+                info(&reader);
+            }
+        }
+        ("list", Some(matches)) if matches.value_of("file") == Some("-") => {
+            list_streaming(std::io::stdin())?;
         }
         ("list", Some(matches)) => {
             let file = File::open(matches.value_of("file").unwrap())?;
@@ -181,22 +231,19 @@ fn do_main(matches: ArgMatches) -> Result<(), Box<dyn std::error::Error>> {
                     write!(
                         &mut tw,
                         "\t{modified}\t{uid}\t{gid}",
-                        modified = entry.modified(),
+                        modified = humantime::format_rfc3339_seconds(entry.modified()),
                         uid = Optional(entry.uid),
                         gid = Optional(entry.gid),
                     )?;
 
-                    match entry.contents() {
-                        rc_zip::EntryContents::Symlink(sl) => {
-                            let mut target = String::new();
-                            rc_zip::EntryReader::new(sl.entry, |offset| {
-                                positioned_io::Cursor::new_pos(&file, dbg!(offset))
-                            })
-                            .read_to_string(&mut target)
-                            .unwrap();
-                            write!(&mut tw, "\t{target}", target = target)?;
-                        }
-                        _ => {}
+                    if let rc_zip::EntryContents::Symlink(sl) = entry.contents() {
+                        let mut target = String::new();
+                        rc_zip::EntryReader::new(sl.entry, |offset| {
+                            positioned_io::Cursor::new_pos(&file, offset)
+                        })
+                        .read_to_string(&mut target)
+                        .unwrap();
+                        write!(&mut tw, "\t{target}", target = target)?;
                     }
                 }
                 writeln!(&mut tw)?;
@@ -206,27 +253,59 @@ fn do_main(matches: ArgMatches) -> Result<(), Box<dyn std::error::Error>> {
         ("extract", Some(matches)) => {
             let file = File::open(matches.value_of("file").unwrap())?;
             let reader = file.read_zip()?;
+            let password = matches.value_of("password");
             info(&reader);
 
-            for entry in reader.entries() {
-                println!("Extracting {}", entry.name());
-                let mut contents = Vec::<u8>::new();
-                entry
-                    .reader(|offset| positioned_io::Cursor::new_pos(&file, offset))
-                    .read_to_end(&mut contents)?;
+            let jobs: usize = matches.value_of("jobs").unwrap().parse()?;
+            let dest = std::path::Path::new(matches.value_of("dest").unwrap());
+            std::fs::create_dir_all(dest)?;
 
-                if let Ok(s) = std::str::from_utf8(&contents[..]) {
-                    println!("contents = {:?}", s);
-                } else {
-                    println!("contents = {:?}", contents);
+            let total_size: u64 = reader.entries().map(|e| e.uncompressed_size).sum();
+            let bar = indicatif::ProgressBar::new(total_size);
+            bar.set_style(
+                indicatif::ProgressStyle::default_bar()
+                    .template("{bar:40} {bytes}/{total_bytes} {msg}")
+                    .unwrap(),
+            );
+            // Reports this entry's own progress via `msg`, and (since
+            // entries extract concurrently) the delta since this entry's
+            // last callback toward the archive-wide total, so the bar
+            // actually animates during extraction rather than jumping from
+            // 0% to 100% once every entry has already finished.
+            let last_reported: std::sync::Mutex<std::collections::HashMap<String, u64>> =
+                std::sync::Mutex::new(std::collections::HashMap::new());
+            let on_progress = |name: &str, current: u64, total: u64| {
+                bar.set_message(format!("{} ({}/{})", name, current, total));
+                let mut last_reported = last_reported.lock().unwrap();
+                let previous = last_reported.insert(name.to_string(), current).unwrap_or(0);
+                bar.inc(current.saturating_sub(previous));
+            };
+
+            for (name, result) in reader.extract_parallel_full(
+                dest,
+                jobs,
+                |offset| positioned_io::Cursor::new_pos(&file, offset),
+                password,
+                Some(&on_progress),
+            ) {
+                if let Err(e) = &result {
+                    eprintln!("Failed to extract {}: {}", name, e);
                 }
             }
+            bar.finish_with_message("done");
         }
         ("compress", Some(matches)) => {
             let files = matches.values_of("files").unwrap();
             let output = matches.value_of("output").unwrap();
-            println!("Should add {:?} to archive {:?}", files, output);
-            unimplemented!();
+
+            let out_file = File::create(output)?;
+            let mut writer = rc_zip::ArchiveWriter::new(out_file);
+
+            for path in files {
+                add_path_to_archive(&mut writer, std::path::Path::new(path))?;
+            }
+
+            writer.finish()?;
         }
         _ => {
             println!("{}", matches.usage());
@@ -237,6 +316,48 @@ fn do_main(matches: ArgMatches) -> Result<(), Box<dyn std::error::Error>> {
     Ok(())
 }
 
+/// Walks `path` (recursing into directories) and adds everything it finds
+/// to `writer`, preserving Unix mode and symlink targets along the way.
+fn add_path_to_archive<W: Write + Seek>(
+    writer: &mut rc_zip::ArchiveWriter<W>,
+    path: &std::path::Path,
+) -> Result<(), Box<dyn std::error::Error>> {
+    use std::os::unix::fs::PermissionsExt;
+
+    let meta = std::fs::symlink_metadata(path)?;
+    let name = path.to_string_lossy().into_owned();
+    let mode = rc_zip::Mode(meta.permissions().mode());
+
+    if meta.file_type().is_symlink() {
+        let target = std::fs::read_link(path)?;
+        let options = rc_zip::EntryOptions::new(mode, rc_zip::Method::Store);
+        let mut entry = writer.start_entry(&name, options)?;
+        entry.write_all(target.to_string_lossy().as_bytes())?;
+        entry.finish()?;
+    } else if meta.is_dir() {
+        let options = rc_zip::EntryOptions::new(mode, rc_zip::Method::Store);
+        writer.start_entry(&format!("{}/", name), options)?.finish()?;
+        for child in std::fs::read_dir(path)? {
+            add_path_to_archive(writer, &child?.path())?;
+        }
+    } else {
+        // Cheap heuristic: already-compressed data rarely shrinks further,
+        // so store it rather than pay for a Deflate pass that won't help.
+        let method = match path.extension().and_then(|e| e.to_str()) {
+            Some("zip") | Some("gz") | Some("jpg") | Some("png") => rc_zip::Method::Store,
+            _ => rc_zip::Method::Deflate,
+        };
+        let options = rc_zip::EntryOptions::new(mode, method);
+        let mut entry = writer.start_entry(&name, options)?;
+        let mut contents = Vec::new();
+        File::open(path)?.read_to_end(&mut contents)?;
+        entry.write_all(&contents)?;
+        entry.finish()?;
+    }
+
+    Ok(())
+}
+
 trait Truncate {
     fn truncate_path(&self, limit: usize) -> String;
 }
@@ -250,16 +371,16 @@ impl Truncate for &str {
             let len_strings = name_tokens.iter().map(|x| x.len()).sum::<usize>()
                 + rest_tokens.iter().map(|x| x.len()).sum::<usize>();
             if len_separators + len_strings < limit {
-                name_tokens.extend(rest_tokens.into_iter());
+                name_tokens.extend(rest_tokens);
                 break name_tokens.join("/");
             }
-            if rest_tokens.len() == 0 {
-                name_tokens.extend(rest_tokens.into_iter());
+            if rest_tokens.is_empty() {
+                name_tokens.extend(rest_tokens);
                 let name = name_tokens.join("/");
                 break name.chars().take(limit - 3).collect::<String>() + "...";
             }
             let token = rest_tokens.pop_front().unwrap();
-            match token.char_indices().skip(1).next() {
+            match token.char_indices().nth(1) {
                 Some((i, _)) => name_tokens.push(&token[..i]),
                 None => name_tokens.push(token),
             }
@@ -0,0 +1,137 @@
+use crate::format::{Method, Mode, Version};
+use std::time::SystemTime;
+
+/// A parsed ZIP archive: the central directory plus whatever trailing
+/// comment followed it.
+pub struct Archive {
+    pub(crate) entries: Vec<Entry>,
+    pub(crate) comment: Option<String>,
+    pub(crate) encoding: &'static str,
+}
+
+impl Archive {
+    pub fn entries(&self) -> impl Iterator<Item = &Entry> {
+        self.entries.iter()
+    }
+
+    pub fn comment(&self) -> Option<&str> {
+        self.comment.as_deref()
+    }
+
+    pub fn encoding(&self) -> &'static str {
+        self.encoding
+    }
+}
+
+/// One record from the central directory.
+#[derive(Debug, Clone)]
+pub struct Entry {
+    pub name: String,
+    pub mode: Mode,
+    pub uid: Option<u32>,
+    pub gid: Option<u32>,
+    pub creator_version: Version,
+    pub reader_version: Version,
+    pub compressed_size: u64,
+    pub uncompressed_size: u64,
+    pub crc32: u32,
+    pub header_offset: u64,
+    pub modified_timestamp: u64,
+    /// The raw DOS last-mod-time field from the central directory record,
+    /// used (instead of the CRC32) to verify a ZipCrypto password when
+    /// `general_purpose_flags` bit 3 says sizes/CRC were deferred to a
+    /// trailing data descriptor.
+    pub(crate) dos_mod_time: u16,
+    pub general_purpose_flags: u16,
+    pub aes_extra: Option<AesExtra>,
+    pub(crate) method: Method,
+}
+
+/// Parsed contents of the WinZip AES extra field (id `0x9901`): the real
+/// compression method hiding behind [`Method::WinzipAes`] and how strong
+/// the encryption is.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AesExtra {
+    pub vendor_version: u16,
+    pub strength: u8,
+    pub actual_method: Method,
+}
+
+impl AesExtra {
+    /// Size in bytes of the salt at the start of the payload, as determined
+    /// by the strength byte. The 2-byte password-verification value that
+    /// follows the salt is not included.
+    pub fn salt_len(&self) -> usize {
+        match self.strength {
+            1 => 8,
+            2 => 12,
+            3 => 16,
+            _ => 16,
+        }
+    }
+
+    pub fn key_len(&self) -> usize {
+        match self.strength {
+            1 => 16,
+            2 => 24,
+            3 => 32,
+            _ => 32,
+        }
+    }
+}
+
+impl Entry {
+    pub fn method(&self) -> Method {
+        self.method
+    }
+
+    pub fn is_encrypted(&self) -> bool {
+        self.general_purpose_flags & 0x1 != 0
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn modified(&self) -> SystemTime {
+        SystemTime::UNIX_EPOCH + std::time::Duration::from_secs(self.modified_timestamp)
+    }
+
+    /// Convenience shorthand for `EntryReader::new(entry, open)`.
+    pub fn reader<'a, F, C>(&'a self, open: F) -> crate::EntryReader<'a, F>
+    where
+        F: FnMut(u64) -> C,
+        C: std::io::Read + 'a,
+    {
+        crate::EntryReader::new(self, open)
+    }
+
+    pub fn contents(&self) -> EntryContents<'_> {
+        if self.mode.is_symlink() {
+            EntryContents::Symlink(Symlink { entry: self })
+        } else if self.mode.is_dir() || self.name.ends_with('/') {
+            EntryContents::Directory(Directory { entry: self })
+        } else {
+            EntryContents::File(FileContents { entry: self })
+        }
+    }
+}
+
+/// The three shapes an entry's central directory record can describe.
+pub enum EntryContents<'a> {
+    File(FileContents<'a>),
+    Directory(Directory<'a>),
+    Symlink(Symlink<'a>),
+}
+
+pub struct FileContents<'a> {
+    pub entry: &'a Entry,
+}
+
+pub struct Directory<'a> {
+    pub entry: &'a Entry,
+}
+
+pub struct Symlink<'a> {
+    pub entry: &'a Entry,
+}
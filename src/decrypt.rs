@@ -0,0 +1,289 @@
+//! Transparent decryption for password-protected entries: legacy PKWARE
+//! ZipCrypto and WinZip AES (extra field `0x9901`).
+
+use crate::archive::AesExtra;
+use crate::error::{Error, Result};
+use std::io::Read;
+
+/// Standard (reflected) CRC-32 update over a single byte, as used both by
+/// the archive's own checksum and by ZipCrypto's key-schedule.
+fn crc32_update(crc: u32, byte: u8) -> u32 {
+    let mut c = (crc ^ byte as u32) & 0xff;
+    for _ in 0..8 {
+        c = if c & 1 != 0 {
+            0xEDB8_8320 ^ (c >> 1)
+        } else {
+            c >> 1
+        };
+    }
+    (crc >> 8) ^ c
+}
+
+/// The three 32-bit keys that make up ZipCrypto's internal state.
+struct ZipCryptoKeys {
+    key0: u32,
+    key1: u32,
+    key2: u32,
+}
+
+impl ZipCryptoKeys {
+    fn new(password: &[u8]) -> Self {
+        let mut keys = ZipCryptoKeys {
+            key0: 0x1234_5678,
+            key1: 0x2345_6789,
+            key2: 0x3456_7890,
+        };
+        for &b in password {
+            keys.update(b);
+        }
+        keys
+    }
+
+    fn update(&mut self, plain_byte: u8) {
+        self.key0 = crc32_update(self.key0, plain_byte);
+        self.key1 = self.key1.wrapping_add(self.key0 & 0xff);
+        self.key1 = self.key1.wrapping_mul(134_775_813).wrapping_add(1);
+        self.key2 = crc32_update(self.key2, (self.key1 >> 24) as u8);
+    }
+
+    fn keystream_byte(&self) -> u8 {
+        let temp = (self.key2 | 2) & 0xffff;
+        ((temp.wrapping_mul(temp ^ 1)) >> 8) as u8
+    }
+
+    fn decrypt_byte(&mut self, cipher_byte: u8) -> u8 {
+        let plain = cipher_byte ^ self.keystream_byte();
+        self.update(plain);
+        plain
+    }
+}
+
+/// Decrypts a legacy ZipCrypto stream on the fly: the first 12 bytes are an
+/// encryption header used only to verify the password, after which the
+/// remaining bytes are the real (still possibly compressed) payload.
+pub struct ZipCryptoReader<R> {
+    inner: R,
+    keys: ZipCryptoKeys,
+}
+
+impl<R: Read> ZipCryptoReader<R> {
+    /// `check_byte` is the high byte of the entry's CRC-32 (or, for entries
+    /// using a data descriptor, the high byte of the DOS mod time) — the
+    /// last byte of the decrypted header must match it.
+    pub fn new(mut inner: R, password: &str, check_byte: u8) -> Result<Self> {
+        let mut keys = ZipCryptoKeys::new(password.as_bytes());
+        let mut header = [0u8; 12];
+        inner.read_exact(&mut header)?;
+        let mut last = 0u8;
+        for b in header.iter_mut() {
+            last = keys.decrypt_byte(*b);
+            *b = last;
+        }
+        if last != check_byte {
+            return Err(Error::Decryption("incorrect password".into()));
+        }
+        Ok(ZipCryptoReader { inner, keys })
+    }
+}
+
+impl<R: Read> Read for ZipCryptoReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let n = self.inner.read(buf)?;
+        for b in &mut buf[..n] {
+            *b = self.keys.decrypt_byte(*b);
+        }
+        Ok(n)
+    }
+}
+
+/// One of the three AES key sizes WinZip supports, dispatched on at
+/// decryption time by [`AesExtra::strength`].
+enum AesKey {
+    Aes128(Box<aes::Aes128>),
+    Aes192(Box<aes::Aes192>),
+    Aes256(Box<aes::Aes256>),
+}
+
+impl AesKey {
+    fn new(key: &[u8]) -> Self {
+        use aes::cipher::KeyInit;
+        match key.len() {
+            16 => AesKey::Aes128(Box::new(aes::Aes128::new_from_slice(key).unwrap())),
+            24 => AesKey::Aes192(Box::new(aes::Aes192::new_from_slice(key).unwrap())),
+            _ => AesKey::Aes256(Box::new(aes::Aes256::new_from_slice(key).unwrap())),
+        }
+    }
+
+    fn encrypt_block(&self, block: &mut [u8; 16]) {
+        use aes::cipher::generic_array::GenericArray;
+        use aes::cipher::BlockEncrypt;
+        let ga = GenericArray::from_mut_slice(block);
+        match self {
+            AesKey::Aes128(c) => c.encrypt_block(ga),
+            AesKey::Aes192(c) => c.encrypt_block(ga),
+            AesKey::Aes256(c) => c.encrypt_block(ga),
+        }
+    }
+}
+
+/// WinZip AES decryption: PBKDF2-derived key material, CTR-mode decryption
+/// and a trailing HMAC-SHA1 that authenticates the ciphertext.
+pub struct WinzipAesReader;
+
+impl WinzipAesReader {
+    /// `data` is the entry's full compressed payload as stored on disk:
+    /// salt, then a 2-byte password-verification value, then the
+    /// ciphertext, then a 10-byte truncated HMAC-SHA1.
+    pub fn decrypt(aes_extra: &AesExtra, password: &str, data: &[u8]) -> Result<Vec<u8>> {
+        let salt_len = aes_extra.salt_len();
+        let key_len = aes_extra.key_len();
+        if data.len() < salt_len + 2 + 10 {
+            return Err(Error::Decryption("truncated AES entry".into()));
+        }
+
+        let salt = &data[..salt_len];
+        let verify = &data[salt_len..salt_len + 2];
+        let ciphertext = &data[salt_len + 2..data.len() - 10];
+        let mac_trailer = &data[data.len() - 10..];
+
+        let mut derived = vec![0u8; key_len * 2 + 2];
+        pbkdf2::pbkdf2_hmac::<sha1::Sha1>(password.as_bytes(), salt, 1000, &mut derived);
+        let (aes_key, rest) = derived.split_at(key_len);
+        let (hmac_key, pv) = rest.split_at(key_len);
+
+        if pv != verify {
+            return Err(Error::Decryption("incorrect password".into()));
+        }
+
+        use hmac::Mac;
+        let mut mac = hmac::Hmac::<sha1::Sha1>::new_from_slice(hmac_key)
+            .map_err(|e| Error::Decryption(e.to_string()))?;
+        mac.update(ciphertext);
+        let computed = mac.finalize().into_bytes();
+        if &computed[..10] != mac_trailer {
+            return Err(Error::Decryption("HMAC authentication failed".into()));
+        }
+
+        let cipher = AesKey::new(aes_key);
+        let mut out = Vec::with_capacity(ciphertext.len());
+        for (block_index, chunk) in ciphertext.chunks(16).enumerate() {
+            let mut counter_block = [0u8; 16];
+            counter_block[..8].copy_from_slice(&(block_index as u64 + 1).to_le_bytes());
+            cipher.encrypt_block(&mut counter_block);
+            for (b, k) in chunk.iter().zip(counter_block.iter()) {
+                out.push(b ^ k);
+            }
+        }
+
+        Ok(out)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::format::Method;
+
+    /// Encrypts `plain` the same way a conforming ZipCrypto writer would,
+    /// so the round trip below exercises the real on-wire format (a 12-byte
+    /// verification header followed by the encrypted payload) rather than
+    /// just mirroring the decoder's own internal state back at it.
+    fn zipcrypto_encrypt(password: &str, check_byte: u8, plain: &[u8]) -> Vec<u8> {
+        let mut keys = ZipCryptoKeys::new(password.as_bytes());
+        let mut header = [0u8; 12];
+        header[11] = check_byte;
+        let mut out = Vec::with_capacity(12 + plain.len());
+        for &b in header.iter().chain(plain) {
+            out.push(b ^ keys.keystream_byte());
+            keys.update(b);
+        }
+        out
+    }
+
+    #[test]
+    fn zipcrypto_round_trips_with_correct_password() {
+        let plain = b"the quick brown fox";
+        let check_byte = 0x42;
+        let ciphertext = zipcrypto_encrypt("hunter2", check_byte, plain);
+
+        let mut reader = ZipCryptoReader::new(&ciphertext[..], "hunter2", check_byte).unwrap();
+        let mut decoded = Vec::new();
+        reader.read_to_end(&mut decoded).unwrap();
+        assert_eq!(decoded, plain);
+    }
+
+    #[test]
+    fn zipcrypto_rejects_wrong_password() {
+        let plain = b"the quick brown fox";
+        let check_byte = 0x42;
+        let ciphertext = zipcrypto_encrypt("hunter2", check_byte, plain);
+
+        match ZipCryptoReader::new(&ciphertext[..], "incorrect", check_byte) {
+            Err(Error::Decryption(_)) => {}
+            other => panic!("expected a decryption error, got {:?}", other.map(|_| ())),
+        }
+    }
+
+    /// Encrypts `plaintext` the same way a conforming WinZip AES writer
+    /// would (PBKDF2-derived keys, AES-CTR, trailing truncated HMAC), so
+    /// the round trip below exercises the real on-wire layout.
+    fn winzip_aes_encrypt(password: &str, salt: &[u8], key_len: usize, plaintext: &[u8]) -> Vec<u8> {
+        let mut derived = vec![0u8; key_len * 2 + 2];
+        pbkdf2::pbkdf2_hmac::<sha1::Sha1>(password.as_bytes(), salt, 1000, &mut derived);
+        let (aes_key, rest) = derived.split_at(key_len);
+        let (hmac_key, pv) = rest.split_at(key_len);
+
+        let cipher = AesKey::new(aes_key);
+        let mut ciphertext = Vec::with_capacity(plaintext.len());
+        for (block_index, chunk) in plaintext.chunks(16).enumerate() {
+            let mut counter_block = [0u8; 16];
+            counter_block[..8].copy_from_slice(&(block_index as u64 + 1).to_le_bytes());
+            cipher.encrypt_block(&mut counter_block);
+            for (b, k) in chunk.iter().zip(counter_block.iter()) {
+                ciphertext.push(b ^ k);
+            }
+        }
+
+        use hmac::Mac;
+        let mut mac = hmac::Hmac::<sha1::Sha1>::new_from_slice(hmac_key).unwrap();
+        mac.update(&ciphertext);
+        let mac_trailer = mac.finalize().into_bytes();
+
+        let mut data = Vec::with_capacity(salt.len() + 2 + ciphertext.len() + 10);
+        data.extend_from_slice(salt);
+        data.extend_from_slice(pv);
+        data.extend_from_slice(&ciphertext);
+        data.extend_from_slice(&mac_trailer[..10]);
+        data
+    }
+
+    #[test]
+    fn winzip_aes_round_trips_with_correct_password() {
+        let aes_extra = AesExtra {
+            vendor_version: 2,
+            strength: 3,
+            actual_method: Method::Store,
+        };
+        let salt = [7u8; 16];
+        let plaintext = b"the quick brown fox jumps over the lazy dog";
+        let data = winzip_aes_encrypt("correct horse", &salt, aes_extra.key_len(), plaintext);
+
+        let decrypted = WinzipAesReader::decrypt(&aes_extra, "correct horse", &data).unwrap();
+        assert_eq!(decrypted, plaintext);
+    }
+
+    #[test]
+    fn winzip_aes_rejects_wrong_password() {
+        let aes_extra = AesExtra {
+            vendor_version: 2,
+            strength: 3,
+            actual_method: Method::Store,
+        };
+        let salt = [7u8; 16];
+        let plaintext = b"the quick brown fox jumps over the lazy dog";
+        let data = winzip_aes_encrypt("correct horse", &salt, aes_extra.key_len(), plaintext);
+
+        let err = WinzipAesReader::decrypt(&aes_extra, "incorrect", &data).unwrap_err();
+        assert!(matches!(err, Error::Decryption(_)));
+    }
+}
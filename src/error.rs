@@ -0,0 +1,54 @@
+use std::fmt;
+
+/// Errors that can occur while reading or writing a ZIP archive.
+#[derive(Debug)]
+pub enum Error {
+    /// The end-of-central-directory record could not be located.
+    InvalidEocd,
+    /// A local file header or central directory record had a bad signature.
+    BadSignature { expected: u32, actual: u32 },
+    /// The archive (or one of its entries) can't be handled: it requires a
+    /// feature this build was not compiled with or that rc-zip does not
+    /// implement, its compressed data or stream is malformed, or (during
+    /// extraction) its path would escape the destination directory.
+    Unsupported(String),
+    /// An encrypted entry could not be decrypted: no password or the wrong
+    /// one was supplied, the payload was truncated, or its authentication
+    /// code did not match.
+    Decryption(String),
+    /// Wraps an underlying I/O error.
+    Io(std::io::Error),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::InvalidEocd => write!(f, "could not locate end-of-central-directory record"),
+            Error::BadSignature { expected, actual } => write!(
+                f,
+                "bad signature: expected {:#010x}, got {:#010x}",
+                expected, actual
+            ),
+            Error::Unsupported(what) => write!(f, "unsupported: {}", what),
+            Error::Decryption(what) => write!(f, "decryption error: {}", what),
+            Error::Io(e) => write!(f, "I/O error: {}", e),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::Io(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<std::io::Error> for Error {
+    fn from(e: std::io::Error) -> Self {
+        Error::Io(e)
+    }
+}
+
+pub type Result<T> = std::result::Result<T, Error>;
@@ -0,0 +1,249 @@
+//! Restoring an entry to the filesystem: directories, regular files and
+//! symlinks, with their Unix mode, timestamp and (when permitted)
+//! ownership preserved.
+
+use crate::archive::{Entry, EntryContents};
+use crate::error::{Error, Result};
+use std::io::Read;
+use std::path::{Component, Path, PathBuf};
+
+/// A `(entry_name, bytes_consumed, total_bytes)` progress callback, shared
+/// between the serial and parallel extraction entry points.
+pub type ProgressCallback<'a> = dyn Fn(&str, u64, u64) + Sync + 'a;
+
+/// Joins `dest` with an entry's name, rejecting ("zip slip") any entry
+/// whose normalized path would land outside of `dest`.
+pub fn safe_dest_path(dest: &Path, name: &str) -> Result<PathBuf> {
+    let mut out = dest.to_path_buf();
+    for component in Path::new(name).components() {
+        match component {
+            Component::Normal(part) => out.push(part),
+            Component::CurDir => {}
+            Component::ParentDir | Component::RootDir | Component::Prefix(_) => {
+                return Err(Error::Unsupported(format!(
+                    "entry {:?} escapes the extraction root",
+                    name
+                )))
+            }
+        }
+    }
+    if !out.starts_with(dest) {
+        return Err(Error::Unsupported(format!(
+            "entry {:?} escapes the extraction root",
+            name
+        )));
+    }
+    Ok(out)
+}
+
+/// Extracts a single entry under `dest`, using `open` to get at its
+/// (possibly compressed) payload. `password` is used for encrypted entries
+/// and ignored otherwise. `on_progress`, if given, is called with
+/// `(entry_name, bytes_consumed, total_bytes)` as the entry is decoded.
+pub fn extract_entry<F, C>(
+    entry: &Entry,
+    dest: &Path,
+    open: F,
+    password: Option<&str>,
+    on_progress: Option<&ProgressCallback<'_>>,
+) -> Result<()>
+where
+    F: FnMut(u64) -> C,
+    C: Read,
+{
+    let out_path = safe_dest_path(dest, entry.name())?;
+    let mut reader = entry.reader(open);
+    if let Some(password) = password {
+        reader = reader.with_password(password);
+    }
+    if let Some(on_progress) = on_progress {
+        let name = entry.name().to_string();
+        reader = reader.with_progress(move |current, total| on_progress(&name, current, total));
+    }
+
+    match entry.contents() {
+        EntryContents::Directory(_) => {
+            std::fs::create_dir_all(&out_path)?;
+            // Metadata is restored later, by `restore_directory_metadata`:
+            // sibling/child entries extracted after this one would just
+            // bump the directory's mtime again via their own
+            // `create_dir_all`/file-creation calls, especially under
+            // parallel extraction where there's no guaranteed ordering
+            // between a directory and its descendants.
+            return Ok(());
+        }
+        EntryContents::Symlink(_) => {
+            if let Some(parent) = out_path.parent() {
+                std::fs::create_dir_all(parent)?;
+            }
+            let mut target = String::new();
+            reader.read_to_string(&mut target)?;
+            let _ = std::fs::remove_file(&out_path);
+            std::os::unix::fs::symlink(target, &out_path)?;
+            restore_symlink_metadata(entry, &out_path)?;
+            return Ok(());
+        }
+        EntryContents::File(_) => {
+            if let Some(parent) = out_path.parent() {
+                std::fs::create_dir_all(parent)?;
+            }
+            let mut out = std::fs::File::create(&out_path)?;
+            std::io::copy(&mut reader, &mut out)?;
+        }
+    }
+
+    restore_metadata(entry, &out_path)?;
+    Ok(())
+}
+
+/// Restores mode/mtime/ownership on every directory entry among `entries`,
+/// deepest paths first, returning one `(entry_name, result)` pair per
+/// directory. Must run after all of those entries' descendants (files,
+/// symlinks and nested directories alike) have already been created, since
+/// creating something inside a directory updates that directory's mtime —
+/// restoring child directories before their parents means each directory's
+/// own metadata write is the last thing to touch it.
+pub fn restore_directory_metadata(entries: &[&Entry], dest: &Path) -> Vec<(String, Result<()>)> {
+    let mut dirs: Vec<&Entry> = entries
+        .iter()
+        .copied()
+        .filter(|e| matches!(e.contents(), EntryContents::Directory(_)))
+        .collect();
+    dirs.sort_by_key(|e| std::cmp::Reverse(e.name().matches('/').count()));
+
+    dirs.into_iter()
+        .map(|entry| {
+            let result = safe_dest_path(dest, entry.name())
+                .and_then(|out_path| restore_metadata(entry, &out_path));
+            (entry.name().to_string(), result)
+        })
+        .collect()
+}
+
+fn restore_metadata(entry: &Entry, path: &Path) -> Result<()> {
+    use std::os::unix::fs::PermissionsExt;
+
+    if entry.mode.0 != 0 {
+        std::fs::set_permissions(path, std::fs::Permissions::from_mode(entry.mode.0 & 0o7777))?;
+    }
+
+    filetime::set_file_mtime(path, filetime::FileTime::from_unix_time(
+        entry.modified_timestamp as i64,
+        0,
+    ))?;
+
+    if let (Some(uid), Some(gid)) = (entry.uid, entry.gid) {
+        // Changing ownership requires privileges we may not have; a
+        // failure here shouldn't abort the rest of the extraction.
+        let _ = nix::unistd::chown(
+            path,
+            Some(nix::unistd::Uid::from_raw(uid)),
+            Some(nix::unistd::Gid::from_raw(gid)),
+        );
+    }
+
+    Ok(())
+}
+
+/// Like `restore_metadata`, but for a symlink itself rather than whatever it
+/// points at: mode bits genuinely can't be set on a symlink (there's no
+/// `lchmod` on Linux), but mtime and ownership still apply to the link, not
+/// its target, so `utimensat`/`fchownat` with `AT_SYMLINK_NOFOLLOW` restore
+/// them without following the link.
+fn restore_symlink_metadata(entry: &Entry, path: &Path) -> Result<()> {
+    let mtime = nix::sys::time::TimeSpec::new(entry.modified_timestamp as i64, 0);
+    nix::sys::stat::utimensat(
+        None,
+        path,
+        &mtime,
+        &mtime,
+        nix::sys::stat::UtimensatFlags::NoFollowSymlink,
+    )
+    .map_err(std::io::Error::from)?;
+
+    if let (Some(uid), Some(gid)) = (entry.uid, entry.gid) {
+        // Changing ownership requires privileges we may not have; a
+        // failure here shouldn't abort the rest of the extraction.
+        let _ = nix::unistd::fchownat(
+            None,
+            path,
+            Some(nix::unistd::Uid::from_raw(uid)),
+            Some(nix::unistd::Gid::from_raw(gid)),
+            nix::unistd::FchownatFlags::NoFollowSymlink,
+        );
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::format::{Method, Mode, Version};
+    use std::os::unix::fs::MetadataExt;
+
+    #[test]
+    fn safe_dest_path_rejects_parent_dir_traversal() {
+        let dest = Path::new("/tmp/rc-zip-extract-root");
+        let err = safe_dest_path(dest, "../../etc/passwd").unwrap_err();
+        assert!(matches!(err, Error::Unsupported(_)));
+    }
+
+    #[test]
+    fn safe_dest_path_rejects_absolute_paths() {
+        let dest = Path::new("/tmp/rc-zip-extract-root");
+        let err = safe_dest_path(dest, "/etc/passwd").unwrap_err();
+        assert!(matches!(err, Error::Unsupported(_)));
+    }
+
+    #[test]
+    fn safe_dest_path_rejects_traversal_hidden_mid_path() {
+        let dest = Path::new("/tmp/rc-zip-extract-root");
+        let err = safe_dest_path(dest, "subdir/../../escape").unwrap_err();
+        assert!(matches!(err, Error::Unsupported(_)));
+    }
+
+    #[test]
+    fn safe_dest_path_accepts_plain_relative_entries() {
+        let dest = Path::new("/tmp/rc-zip-extract-root");
+        let path = safe_dest_path(dest, "a/b/c.txt").unwrap();
+        assert_eq!(path, dest.join("a/b/c.txt"));
+    }
+
+    #[test]
+    fn restore_symlink_metadata_sets_mtime_without_following() {
+        let dir = tempfile::tempdir().unwrap();
+        let target_path = dir.path().join("target.txt");
+        std::fs::write(&target_path, b"hi").unwrap();
+        let link_path = dir.path().join("link");
+        std::os::unix::fs::symlink(&target_path, &link_path).unwrap();
+
+        let entry = Entry {
+            name: "link".to_string(),
+            mode: Mode(0o120777),
+            uid: None,
+            gid: None,
+            creator_version: Version { host: 3, spec: 20 },
+            reader_version: Version { host: 0, spec: 20 },
+            compressed_size: 0,
+            uncompressed_size: 0,
+            crc32: 0,
+            header_offset: 0,
+            modified_timestamp: 1_000_000_000,
+            dos_mod_time: 0,
+            general_purpose_flags: 0,
+            aes_extra: None,
+            method: Method::Store,
+        };
+
+        restore_symlink_metadata(&entry, &link_path).unwrap();
+
+        let link_meta = std::fs::symlink_metadata(&link_path).unwrap();
+        assert_eq!(link_meta.mtime(), 1_000_000_000);
+
+        // The link's target is untouched: utimensat with AT_SYMLINK_NOFOLLOW
+        // must act on the link itself, not follow it.
+        let target_meta = std::fs::metadata(&target_path).unwrap();
+        assert_ne!(target_meta.mtime(), 1_000_000_000);
+    }
+}
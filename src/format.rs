@@ -0,0 +1,119 @@
+//! On-disk structures and constants shared by the reader and writer.
+
+use std::fmt;
+
+pub const LOCAL_FILE_HEADER_SIG: u32 = 0x0403_4b50;
+pub const CENTRAL_DIRECTORY_ENTRY_SIG: u32 = 0x0201_4b50;
+pub const EOCD_SIG: u32 = 0x0605_4b50;
+pub const EOCD64_SIG: u32 = 0x0606_4b50;
+pub const EOCD64_LOCATOR_SIG: u32 = 0x0706_4b50;
+pub const DATA_DESCRIPTOR_SIG: u32 = 0x0807_4b50;
+
+/// Threshold at or above which a field must be stored in the ZIP64 extra field
+/// instead of the classic 32-bit record.
+pub const ZIP64_THRESHOLD: u64 = 0xFFFF_FFFF;
+
+/// Extra-field tag for the ZIP64 extended-information field.
+pub const ZIP64_EXTRA_ID: u16 = 0x0001;
+
+/// Extra-field tag for the Info-ZIP "new Unix" field, which carries the
+/// owning uid/gid as variable-length little-endian integers.
+pub const UNIX_EXTRA_ID: u16 = 0x7875;
+
+/// The compression method of an entry, as found in the local file header
+/// and central directory record.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum Method {
+    #[default]
+    Store,
+    Deflate,
+    Bzip2,
+    Lzma,
+    Zstd,
+    WinzipAes,
+    Unsupported(u16),
+}
+
+impl Method {
+    pub fn from_u16(raw: u16) -> Self {
+        match raw {
+            0 => Method::Store,
+            8 => Method::Deflate,
+            12 => Method::Bzip2,
+            14 => Method::Lzma,
+            93 => Method::Zstd,
+            99 => Method::WinzipAes,
+            other => Method::Unsupported(other),
+        }
+    }
+
+    pub fn to_u16(self) -> u16 {
+        match self {
+            Method::Store => 0,
+            Method::Deflate => 8,
+            Method::Bzip2 => 12,
+            Method::Lzma => 14,
+            Method::Zstd => 93,
+            Method::WinzipAes => 99,
+            Method::Unsupported(raw) => raw,
+        }
+    }
+}
+
+/// A "version made by" / "version needed to extract" field: a spec version
+/// paired with the host OS that produced (or is required to read) the entry.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Version {
+    pub host: u8,
+    pub spec: u8,
+}
+
+impl Version {
+    pub fn from_u16(raw: u16) -> Self {
+        Version {
+            host: (raw >> 8) as u8,
+            spec: (raw & 0xff) as u8,
+        }
+    }
+
+    pub fn to_u16(self) -> u16 {
+        ((self.host as u16) << 8) | self.spec as u16
+    }
+}
+
+/// Unix permission bits, as stashed in the high 16 bits of the central
+/// directory's "external attributes" field when `creator_version.host`
+/// indicates a Unix-like creator.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Mode(pub u32);
+
+impl Mode {
+    pub fn from_external_attrs(creator_host: u8, external_attrs: u32) -> Self {
+        const HOST_UNIX: u8 = 3;
+        if creator_host == HOST_UNIX {
+            Mode(external_attrs >> 16)
+        } else {
+            Mode(0)
+        }
+    }
+
+    pub fn to_external_attrs(self) -> u32 {
+        self.0 << 16
+    }
+
+    pub fn is_symlink(self) -> bool {
+        const S_IFLNK: u32 = 0o120000;
+        self.0 & 0o170000 == S_IFLNK
+    }
+
+    pub fn is_dir(self) -> bool {
+        const S_IFDIR: u32 = 0o040000;
+        self.0 & 0o170000 == S_IFDIR
+    }
+}
+
+impl fmt::Display for Mode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:o}", self.0 & 0o7777)
+    }
+}
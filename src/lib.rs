@@ -0,0 +1,24 @@
+//! rc-zip: a pure-Rust ZIP reader and writer.
+
+mod archive;
+mod decrypt;
+mod error;
+pub mod extract;
+mod format;
+#[cfg(feature = "lzma")]
+mod lzma;
+mod parallel;
+mod reader;
+mod streaming;
+mod write;
+
+pub use archive::{AesExtra, Archive, Directory, Entry, EntryContents, FileContents, Symlink};
+pub use error::{Error, Result};
+pub use format::{Method, Mode, Version};
+pub use reader::{EntryReader, ReadZip};
+pub use streaming::{StreamingArchiveReader, StreamingEntry};
+pub use write::{ArchiveWriter, EntryOptions, EntryWriter};
+
+pub mod prelude {
+    pub use crate::reader::ReadZip;
+}
@@ -0,0 +1,94 @@
+//! LZMA (ZIP method 14) decoding, via `lzma-rs`.
+//!
+//! Entries using this method prefix the raw LZMA stream with a small
+//! header: a 2-byte LZMA SDK version, a 2-byte properties size (always 5),
+//! and the 5-byte `lclppb`/dictionary-size properties block that a
+//! standalone `.lzma` file would carry in the same position — followed,
+//! in a `.lzma` file but not here, by an 8-byte uncompressed size. The ZIP
+//! central directory already tells us the uncompressed size, so we hand
+//! it to `lzma-rs` via `UnpackedSize::UseProvided` instead of expecting it
+//! on the wire.
+
+use crate::error::{Error, Result};
+use lzma_rs::decompress::{Options, UnpackedSize};
+use std::io::{BufReader, Cursor, Read};
+
+pub struct LzmaDecoder {
+    inner: Cursor<Vec<u8>>,
+}
+
+impl LzmaDecoder {
+    pub fn new<R: Read>(mut r: R, uncompressed_size: u64) -> Result<Self> {
+        let mut header = [0u8; 4];
+        r.read_exact(&mut header)?;
+        let props_size = u16::from_le_bytes([header[2], header[3]]) as usize;
+        let mut props = vec![0u8; props_size];
+        r.read_exact(&mut props)?;
+
+        let options = Options {
+            unpacked_size: UnpackedSize::UseProvided(Some(uncompressed_size)),
+            ..Default::default()
+        };
+
+        let mut input = BufReader::new(Cursor::new(props).chain(r));
+        let mut decompressed = Vec::new();
+        lzma_rs::lzma_decompress_with_options(&mut input, &mut decompressed, &options)
+            .map_err(|e| Error::Unsupported(format!("LZMA decode failed: {:?}", e)))?;
+
+        Ok(LzmaDecoder {
+            inner: Cursor::new(decompressed),
+        })
+    }
+}
+
+impl Read for LzmaDecoder {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        self.inner.read(buf)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::LzmaDecoder;
+    use lzma_rs::compress::{Options, UnpackedSize};
+    use std::io::Read;
+
+    /// Builds the on-wire format `LzmaDecoder::new` expects: the 4-byte
+    /// version+propsize header, the 5-byte properties block, then a raw
+    /// LZMA stream with no trailing size field (matching `UseProvided` on
+    /// the decode side, the uncompressed size is supplied out of band).
+    fn encode_for_decoder(plaintext: &[u8]) -> Vec<u8> {
+        let options = Options {
+            unpacked_size: UnpackedSize::SkipWritingToHeader,
+        };
+        let mut raw = Vec::new();
+        lzma_rs::lzma_compress_with_options(&mut &plaintext[..], &mut raw, &options).unwrap();
+
+        // `lzma_rs` writes the 5-byte properties block at the front of its
+        // output, with no size field after it when told not to write the
+        // unpacked size; split it back
+        // off so we can prepend our own 4-byte version+propsize header.
+        let props = raw[..5].to_vec();
+        let stream = raw[5..].to_vec();
+
+        let mut out = Vec::new();
+        out.extend_from_slice(&[0u8, 0u8]); // LZMA SDK version, unused by the decoder
+        out.extend_from_slice(&(props.len() as u16).to_le_bytes());
+        out.extend_from_slice(&props);
+        out.extend_from_slice(&stream);
+        out
+    }
+
+    #[test]
+    fn round_trips_through_lzma_decoder() {
+        let plaintext = b"the quick brown fox jumps over the lazy dog, repeatedly, repeatedly";
+        let encoded = encode_for_decoder(plaintext);
+
+        let mut decoded = Vec::new();
+        LzmaDecoder::new(encoded.as_slice(), plaintext.len() as u64)
+            .unwrap()
+            .read_to_end(&mut decoded)
+            .unwrap();
+        assert_eq!(decoded, plaintext);
+    }
+}
@@ -0,0 +1,476 @@
+use crate::archive::{AesExtra, Archive, Entry};
+use crate::decrypt::{WinzipAesReader, ZipCryptoReader};
+use crate::error::{Error, Result};
+use crate::format::{
+    Method, Mode, Version, CENTRAL_DIRECTORY_ENTRY_SIG, EOCD_SIG, UNIX_EXTRA_ID, ZIP64_EXTRA_ID,
+    ZIP64_THRESHOLD,
+};
+use std::io::{Read, Seek, SeekFrom};
+
+const AES_EXTRA_ID: u16 = 0x9901;
+
+/// How far from the end of the file we're willing to scan looking for the
+/// end-of-central-directory record (22 bytes of fixed fields plus the
+/// largest possible comment).
+const EOCD_SEARCH_WINDOW: u64 = 22 + 0xFFFF;
+
+/// Extension trait that turns a seekable reader into a parsed [`Archive`].
+pub trait ReadZip {
+    fn read_zip(&self) -> Result<Archive>;
+}
+
+impl ReadZip for std::fs::File {
+    fn read_zip(&self) -> Result<Archive> {
+        let mut file = self.try_clone()?;
+        read_zip_from(&mut file)
+    }
+}
+
+fn read_zip_from<R: Read + Seek>(r: &mut R) -> Result<Archive> {
+    let file_len = r.seek(SeekFrom::End(0))?;
+    let window = EOCD_SEARCH_WINDOW.min(file_len);
+    r.seek(SeekFrom::End(-(window as i64)))?;
+    let mut buf = vec![0u8; window as usize];
+    r.read_exact(&mut buf)?;
+
+    let eocd_pos = buf
+        .windows(4)
+        .rposition(|w| u32::from_le_bytes([w[0], w[1], w[2], w[3]]) == EOCD_SIG)
+        .ok_or(Error::InvalidEocd)?;
+    let eocd = &buf[eocd_pos..];
+
+    let entry_count = u16::from_le_bytes([eocd[10], eocd[11]]) as usize;
+    let cd_size = u32::from_le_bytes([eocd[12], eocd[13], eocd[14], eocd[15]]) as u64;
+    let cd_offset = u32::from_le_bytes([eocd[16], eocd[17], eocd[18], eocd[19]]) as u64;
+    let comment_len = u16::from_le_bytes([eocd[20], eocd[21]]) as usize;
+    let comment = if comment_len > 0 {
+        Some(String::from_utf8_lossy(&eocd[22..22 + comment_len]).into_owned())
+    } else {
+        None
+    };
+
+    r.seek(SeekFrom::Start(cd_offset))?;
+    let mut cd_buf = vec![0u8; cd_size as usize];
+    r.read_exact(&mut cd_buf)?;
+
+    let mut entries = Vec::with_capacity(entry_count);
+    let mut pos = 0usize;
+    for _ in 0..entry_count {
+        let rec = &cd_buf[pos..];
+        let sig = u32::from_le_bytes([rec[0], rec[1], rec[2], rec[3]]);
+        if sig != CENTRAL_DIRECTORY_ENTRY_SIG {
+            return Err(Error::BadSignature {
+                expected: CENTRAL_DIRECTORY_ENTRY_SIG,
+                actual: sig,
+            });
+        }
+        let creator_version = Version::from_u16(u16::from_le_bytes([rec[4], rec[5]]));
+        let reader_version = Version::from_u16(u16::from_le_bytes([rec[6], rec[7]]));
+        let method = Method::from_u16(u16::from_le_bytes([rec[10], rec[11]]));
+        let mod_time = u16::from_le_bytes([rec[12], rec[13]]);
+        let mod_date = u16::from_le_bytes([rec[14], rec[15]]);
+        let crc32 = u32::from_le_bytes([rec[16], rec[17], rec[18], rec[19]]);
+        let compressed_size = u32::from_le_bytes([rec[20], rec[21], rec[22], rec[23]]) as u64;
+        let uncompressed_size = u32::from_le_bytes([rec[24], rec[25], rec[26], rec[27]]) as u64;
+        let name_len = u16::from_le_bytes([rec[28], rec[29]]) as usize;
+        let extra_len = u16::from_le_bytes([rec[30], rec[31]]) as usize;
+        let entry_comment_len = u16::from_le_bytes([rec[32], rec[33]]) as usize;
+        let external_attrs = u32::from_le_bytes([rec[38], rec[39], rec[40], rec[41]]);
+        let header_offset = u32::from_le_bytes([rec[42], rec[43], rec[44], rec[45]]) as u64;
+        let name_start = 46;
+        let name =
+            String::from_utf8_lossy(&rec[name_start..name_start + name_len]).into_owned();
+
+        let general_purpose_flags = u16::from_le_bytes([rec[8], rec[9]]);
+        let extra_start = name_start + name_len;
+        let extra = &rec[extra_start..extra_start + extra_len];
+        let aes_extra = parse_aes_extra(extra);
+        let (uid, gid) = parse_unix_extra(extra);
+        let (compressed_size, uncompressed_size, header_offset) =
+            parse_zip64_extra(extra, compressed_size, uncompressed_size, header_offset);
+
+        entries.push(Entry {
+            name,
+            mode: Mode::from_external_attrs(creator_version.host, external_attrs),
+            uid,
+            gid,
+            creator_version,
+            reader_version,
+            compressed_size,
+            uncompressed_size,
+            crc32,
+            header_offset,
+            modified_timestamp: dos_datetime_to_unix(mod_date, mod_time),
+            dos_mod_time: mod_time,
+            general_purpose_flags,
+            aes_extra,
+            method,
+        });
+
+        pos += name_start + name_len + extra_len + entry_comment_len;
+    }
+
+    Ok(Archive {
+        entries,
+        comment,
+        encoding: "utf-8",
+    })
+}
+
+/// Looks for a WinZip AES extra field (id `0x9901`) among an entry's extra
+/// field records and, if found, parses the real method and key strength
+/// it hides behind [`Method::WinzipAes`].
+fn parse_aes_extra(mut extra: &[u8]) -> Option<AesExtra> {
+    while extra.len() >= 4 {
+        let id = u16::from_le_bytes([extra[0], extra[1]]);
+        let size = u16::from_le_bytes([extra[2], extra[3]]) as usize;
+        let body = extra.get(4..4 + size)?;
+        if id == AES_EXTRA_ID && body.len() >= 7 {
+            let vendor_version = u16::from_le_bytes([body[0], body[1]]);
+            let strength = body[4];
+            let actual_method = Method::from_u16(u16::from_le_bytes([body[5], body[6]]));
+            return Some(AesExtra {
+                vendor_version,
+                strength,
+                actual_method,
+            });
+        }
+        extra = &extra[4 + size..];
+    }
+    None
+}
+
+/// Looks for a ZIP64 extra field (id `0x0001`) and, for each of the three
+/// 32-bit fields that read back as the `0xFFFFFFFF` sentinel, substitutes
+/// the real 64-bit value carried there — in the fixed order APPNOTE 4.5.3
+/// mandates: uncompressed size, compressed size, then header offset.
+pub(crate) fn parse_zip64_extra(
+    mut extra: &[u8],
+    compressed_size: u64,
+    uncompressed_size: u64,
+    header_offset: u64,
+) -> (u64, u64, u64) {
+    while extra.len() >= 4 {
+        let id = u16::from_le_bytes([extra[0], extra[1]]);
+        let size = u16::from_le_bytes([extra[2], extra[3]]) as usize;
+        let body = match extra.get(4..4 + size) {
+            Some(b) => b,
+            None => break,
+        };
+        if id == ZIP64_EXTRA_ID {
+            let mut body = body;
+            let mut uncompressed_size = uncompressed_size;
+            let mut compressed_size = compressed_size;
+            let mut header_offset = header_offset;
+            if uncompressed_size == ZIP64_THRESHOLD && body.len() >= 8 {
+                uncompressed_size = u64::from_le_bytes(body[..8].try_into().unwrap());
+                body = &body[8..];
+            }
+            if compressed_size == ZIP64_THRESHOLD && body.len() >= 8 {
+                compressed_size = u64::from_le_bytes(body[..8].try_into().unwrap());
+                body = &body[8..];
+            }
+            if header_offset == ZIP64_THRESHOLD && body.len() >= 8 {
+                header_offset = u64::from_le_bytes(body[..8].try_into().unwrap());
+            }
+            return (compressed_size, uncompressed_size, header_offset);
+        }
+        extra = &extra[4 + size..];
+    }
+    (compressed_size, uncompressed_size, header_offset)
+}
+
+/// Looks for the Info-ZIP "new Unix" extra field (id `0x7875`), which
+/// stores the owning uid/gid as variable-length little-endian integers.
+fn parse_unix_extra(mut extra: &[u8]) -> (Option<u32>, Option<u32>) {
+    while extra.len() >= 4 {
+        let id = u16::from_le_bytes([extra[0], extra[1]]);
+        let size = u16::from_le_bytes([extra[2], extra[3]]) as usize;
+        let body = match extra.get(4..4 + size) {
+            Some(b) => b,
+            None => break,
+        };
+        if id == UNIX_EXTRA_ID && body.len() >= 2 {
+            let uid_size = body[1] as usize;
+            let uid_start = 2;
+            let uid = le_bytes_to_u32(body.get(uid_start..uid_start + uid_size));
+            let gid_size_pos = uid_start + uid_size;
+            let gid_size = *body.get(gid_size_pos).unwrap_or(&0) as usize;
+            let gid_start = gid_size_pos + 1;
+            let gid = le_bytes_to_u32(body.get(gid_start..gid_start + gid_size));
+            return (uid, gid);
+        }
+        extra = &extra[4 + size..];
+    }
+    (None, None)
+}
+
+fn le_bytes_to_u32(bytes: Option<&[u8]>) -> Option<u32> {
+    let bytes = bytes?;
+    let mut buf = [0u8; 4];
+    buf[..bytes.len().min(4)].copy_from_slice(&bytes[..bytes.len().min(4)]);
+    Some(u32::from_le_bytes(buf))
+}
+
+fn dos_datetime_to_unix(date: u16, time: u16) -> u64 {
+    let day = (date & 0x1f) as i64;
+    let month = ((date >> 5) & 0xf) as i64;
+    let year = 1980 + (date >> 9) as i64;
+    let second = ((time & 0x1f) * 2) as u64;
+    let minute = ((time >> 5) & 0x3f) as u64;
+    let hour = (time >> 11) as u64;
+
+    let days_since_epoch = days_from_civil(year, month, day);
+    (days_since_epoch * 86400) as u64 + hour * 3600 + minute * 60 + second
+}
+
+/// Converts a (year, month, day) calendar date into a day count relative to
+/// the Unix epoch (1970-01-01), accounting for actual days-per-month and
+/// leap years. Based on Howard Hinnant's `days_from_civil` algorithm.
+fn days_from_civil(year: i64, month: i64, day: i64) -> i64 {
+    let y = if month <= 2 { year - 1 } else { year };
+    let era = if y >= 0 { y } else { y - 399 } / 400;
+    let yoe = y - era * 400;
+    let mp = (month + 9) % 12;
+    let doy = (153 * mp + 2) / 5 + day - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    era * 146097 + doe - 719468
+}
+
+/// Reads (and decompresses/decrypts) the contents of a single entry, given
+/// a way to seek to an arbitrary offset in the underlying storage.
+pub struct EntryReader<'a, F> {
+    entry: &'a Entry,
+    open: F,
+    password: Option<String>,
+    decoder: Option<Box<dyn Read + 'a>>,
+    consumed: u64,
+    progress: Option<Box<dyn FnMut(u64, u64) + 'a>>,
+}
+
+impl<'a, F, C> EntryReader<'a, F>
+where
+    F: FnMut(u64) -> C,
+    C: Read + 'a,
+{
+    pub fn new(entry: &'a Entry, open: F) -> Self {
+        EntryReader {
+            entry,
+            open,
+            password: None,
+            decoder: None,
+            consumed: 0,
+            progress: None,
+        }
+    }
+
+    /// Supplies the password needed to decrypt a ZipCrypto- or WinZip
+    /// AES-encrypted entry. Has no effect on entries that aren't encrypted.
+    pub fn with_password(mut self, password: impl Into<String>) -> Self {
+        self.password = Some(password.into());
+        self
+    }
+
+    /// Registers a callback invoked with `(bytes_consumed, total_bytes)` as
+    /// decompressed bytes flow through `read`, so callers can drive a
+    /// progress bar without the crate depending on any particular one.
+    pub fn with_progress(mut self, callback: impl FnMut(u64, u64) + 'a) -> Self {
+        self.progress = Some(Box::new(callback));
+        self
+    }
+
+    /// Skips over the local file header to find where the entry's payload
+    /// actually starts (the header's filename/extra-field lengths can
+    /// differ from the central directory's).
+    fn payload_offset(cursor: &mut C) -> Result<u64> {
+        let mut header = [0u8; 30];
+        cursor.read_exact(&mut header)?;
+        let name_len = u16::from_le_bytes([header[26], header[27]]) as u64;
+        let extra_len = u16::from_le_bytes([header[28], header[29]]) as u64;
+        Ok(30 + name_len + extra_len)
+    }
+
+    fn decoder_inner(&mut self) -> Result<Box<dyn Read + 'a>> {
+        let mut cursor = (self.open)(self.entry.header_offset);
+        let skip = Self::payload_offset(&mut cursor)?;
+        let mut buf = vec![0u8; skip as usize - 30];
+        cursor.read_exact(&mut buf)?;
+        let limited = cursor.take(self.entry.compressed_size);
+
+        if let Some(aes) = self.entry.aes_extra {
+            let password = self
+                .password
+                .as_deref()
+                .ok_or_else(|| Error::Decryption("password required".into()))?;
+            let mut ciphertext = Vec::new();
+            let mut limited = limited;
+            limited.read_to_end(&mut ciphertext)?;
+            let plaintext = WinzipAesReader::decrypt(&aes, password, &ciphertext)?;
+            return decode_by_method(
+                aes.actual_method,
+                std::io::Cursor::new(plaintext),
+                self.entry.uncompressed_size,
+            );
+        }
+
+        if self.entry.is_encrypted() {
+            let password = self
+                .password
+                .as_deref()
+                .ok_or_else(|| Error::Decryption("password required".into()))?;
+            // When bit 3 is set, the CRC (and sizes) were deferred to a
+            // trailing data descriptor and weren't known yet when the
+            // encryption header was written, so PKWARE falls back to the
+            // high byte of the DOS mod time for the verification byte.
+            let check_byte = if self.entry.general_purpose_flags & 0x08 != 0 {
+                ((self.entry.dos_mod_time >> 8) & 0xff) as u8
+            } else {
+                ((self.entry.crc32 >> 24) & 0xff) as u8
+            };
+            let decrypted = ZipCryptoReader::new(limited, password, check_byte)?;
+            return decode_by_method(self.entry.method(), decrypted, self.entry.uncompressed_size);
+        }
+
+        decode_by_method(self.entry.method(), limited, self.entry.uncompressed_size)
+    }
+}
+
+pub(crate) fn decode_by_method<'a, R: Read + 'a>(
+    method: Method,
+    r: R,
+    #[cfg_attr(not(feature = "lzma"), allow(unused_variables))] uncompressed_size: u64,
+) -> Result<Box<dyn Read + 'a>> {
+    match method {
+        Method::Store => Ok(Box::new(r)),
+        Method::Deflate => Ok(Box::new(flate2::read::DeflateDecoder::new(r))),
+        #[cfg(feature = "bzip2")]
+        Method::Bzip2 => Ok(Box::new(bzip2::read::BzDecoder::new(r))),
+        #[cfg(feature = "lzma")]
+        Method::Lzma => Ok(Box::new(crate::lzma::LzmaDecoder::new(r, uncompressed_size)?)),
+        #[cfg(feature = "zstd")]
+        Method::Zstd => Ok(Box::new(zstd::stream::Decoder::new(r)?)),
+        other => Err(Error::Unsupported(format!("method {:?}", other))),
+    }
+}
+
+impl<'a, F, C> Read for EntryReader<'a, F>
+where
+    F: FnMut(u64) -> C,
+    C: Read + 'a,
+{
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        if self.decoder.is_none() {
+            self.decoder = Some(
+                self.decoder_inner()
+                    .map_err(std::io::Error::other)?,
+            );
+        }
+        let n = self.decoder.as_mut().unwrap().read(buf)?;
+        if n > 0 {
+            self.consumed += n as u64;
+            if let Some(progress) = self.progress.as_mut() {
+                progress(self.consumed, self.entry.uncompressed_size);
+            }
+        }
+        Ok(n)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::dos_datetime_to_unix;
+
+    #[test]
+    fn dos_datetime_to_unix_matches_known_timestamp() {
+        // 2020-12-25 10:00:00 UTC, DOS-encoded.
+        let date = ((2020 - 1980) << 9) | (12 << 5) | 25;
+        let time = 10 << 11;
+        assert_eq!(dos_datetime_to_unix(date, time), 1608890400);
+    }
+
+    #[test]
+    fn dos_datetime_to_unix_handles_leap_year_day() {
+        // 2020-02-29 00:00:00 UTC, DOS-encoded.
+        let date = ((2020 - 1980) << 9) | (2 << 5) | 29;
+        assert_eq!(dos_datetime_to_unix(date, 0), 1582934400);
+    }
+
+    #[cfg(feature = "bzip2")]
+    #[test]
+    fn decode_by_method_round_trips_bzip2() {
+        use super::{decode_by_method, Method};
+        use std::io::{Read, Write};
+
+        let plaintext = b"the quick brown fox jumps over the lazy dog, repeatedly, repeatedly";
+        let mut encoder =
+            bzip2::write::BzEncoder::new(Vec::new(), bzip2::Compression::default());
+        encoder.write_all(plaintext).unwrap();
+        let compressed = encoder.finish().unwrap();
+
+        let mut decoded = Vec::new();
+        decode_by_method(Method::Bzip2, compressed.as_slice(), plaintext.len() as u64)
+            .unwrap()
+            .read_to_end(&mut decoded)
+            .unwrap();
+        assert_eq!(decoded, plaintext);
+    }
+
+    #[test]
+    fn with_progress_reports_increasing_counts_against_a_fixed_total() {
+        use crate::prelude::*;
+        use crate::{ArchiveWriter, EntryOptions, Method, Mode};
+        use std::cell::RefCell;
+        use std::io::Read as _;
+
+        let tmp = tempfile::tempdir().unwrap();
+        let zip_path = tmp.path().join("fixture.zip");
+        let payload: Vec<u8> = (0..10_000).map(|b| (b % 251) as u8).collect();
+        {
+            let file = std::fs::File::create(&zip_path).unwrap();
+            let mut writer = ArchiveWriter::new(file);
+            let options = EntryOptions::new(Mode(0o644), Method::Deflate);
+            let mut entry = writer.start_entry("big.bin", options).unwrap();
+            entry.write_all(&payload).unwrap();
+            entry.finish().unwrap();
+            writer.finish().unwrap();
+        }
+
+        let file = std::fs::File::open(&zip_path).unwrap();
+        let archive = file.read_zip().unwrap();
+        let entry = archive.entries().next().unwrap();
+
+        let updates = RefCell::new(Vec::new());
+        let mut reader = entry
+            .reader(|offset| positioned_io::Cursor::new_pos(&file, offset))
+            .with_progress(|current, total| updates.borrow_mut().push((current, total)));
+
+        let mut decoded = Vec::new();
+        reader.read_to_end(&mut decoded).unwrap();
+        assert_eq!(decoded, payload);
+        drop(reader);
+
+        let updates = updates.into_inner();
+        assert!(!updates.is_empty());
+        assert!(updates.iter().all(|&(_, total)| total == payload.len() as u64));
+        assert!(updates.windows(2).all(|w| w[0].0 < w[1].0));
+        assert_eq!(updates.last().unwrap().0, payload.len() as u64);
+    }
+
+    #[cfg(feature = "zstd")]
+    #[test]
+    fn decode_by_method_round_trips_zstd() {
+        use super::{decode_by_method, Method};
+        use std::io::Read;
+
+        let plaintext = b"the quick brown fox jumps over the lazy dog, repeatedly, repeatedly";
+        let compressed = zstd::stream::encode_all(plaintext.as_slice(), 0).unwrap();
+
+        let mut decoded = Vec::new();
+        decode_by_method(Method::Zstd, compressed.as_slice(), plaintext.len() as u64)
+            .unwrap()
+            .read_to_end(&mut decoded)
+            .unwrap();
+        assert_eq!(decoded, plaintext);
+    }
+
+}
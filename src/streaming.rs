@@ -0,0 +1,263 @@
+//! A forward-only archive reader for sources that can't seek (stdin,
+//! pipes). Unlike [`crate::ReadZip`], which locates the authoritative
+//! central directory by seeking from the end, this walks local file
+//! headers from the front and never sees the central directory at all.
+
+use crate::error::{Error, Result};
+use crate::format::{Method, Version, CENTRAL_DIRECTORY_ENTRY_SIG, DATA_DESCRIPTOR_SIG, LOCAL_FILE_HEADER_SIG};
+use crate::reader::{decode_by_method, parse_zip64_extra};
+use std::io::{Cursor, Read};
+
+/// General purpose bit flag 3: sizes are unknown in the local header and
+/// instead follow the entry's payload in a data descriptor record.
+const FLAG_DATA_DESCRIPTOR: u16 = 0x08;
+
+/// Reads entries one at a time from any [`Read`], in the order they
+/// appear in the file. Stops (returning `None`) at the end of input or at
+/// the first central directory record; any other signature where a local
+/// file header was expected is a [`Error::BadSignature`].
+pub struct StreamingArchiveReader<R> {
+    inner: R,
+}
+
+impl<R: Read> StreamingArchiveReader<R> {
+    pub fn new(inner: R) -> Self {
+        StreamingArchiveReader { inner }
+    }
+
+    /// Parses and returns the next entry's header, along with a reader
+    /// over its (decompressed) contents. Must be fully drained (or at
+    /// least its compressed_size consumed) before calling this again.
+    pub fn next_entry(&mut self) -> Result<Option<StreamingEntry<'_>>> {
+        let mut sig_buf = [0u8; 4];
+        if let Err(e) = self.inner.read_exact(&mut sig_buf) {
+            if e.kind() == std::io::ErrorKind::UnexpectedEof {
+                return Ok(None);
+            }
+            return Err(e.into());
+        }
+        let sig = u32::from_le_bytes(sig_buf);
+        if sig != LOCAL_FILE_HEADER_SIG {
+            if sig == CENTRAL_DIRECTORY_ENTRY_SIG {
+                return Ok(None);
+            }
+            return Err(Error::BadSignature {
+                expected: LOCAL_FILE_HEADER_SIG,
+                actual: sig,
+            });
+        }
+
+        let mut rest = [0u8; 26];
+        self.inner.read_exact(&mut rest)?;
+        let _reader_version = Version::from_u16(u16::from_le_bytes([rest[0], rest[1]]));
+        let flags = u16::from_le_bytes([rest[2], rest[3]]);
+        let method = Method::from_u16(u16::from_le_bytes([rest[4], rest[5]]));
+        let mut crc32 = u32::from_le_bytes([rest[10], rest[11], rest[12], rest[13]]);
+        let mut compressed_size = u32::from_le_bytes([rest[14], rest[15], rest[16], rest[17]]) as u64;
+        let mut uncompressed_size = u32::from_le_bytes([rest[18], rest[19], rest[20], rest[21]]) as u64;
+        let name_len = u16::from_le_bytes([rest[22], rest[23]]) as usize;
+        let extra_len = u16::from_le_bytes([rest[24], rest[25]]) as usize;
+
+        let mut name_buf = vec![0u8; name_len];
+        self.inner.read_exact(&mut name_buf)?;
+        let name = String::from_utf8_lossy(&name_buf).into_owned();
+
+        let mut extra_buf = vec![0u8; extra_len];
+        self.inner.read_exact(&mut extra_buf)?;
+
+        // Local headers carry no header offset, so the third field here is
+        // unused; pass 0, which never matches the ZIP64 sentinel and so is
+        // left untouched.
+        (compressed_size, uncompressed_size, _) =
+            parse_zip64_extra(&extra_buf, compressed_size, uncompressed_size, 0);
+
+        let decoder: Box<dyn Read + '_> = if flags & FLAG_DATA_DESCRIPTOR != 0 {
+            let (payload, real_crc32, real_compressed, real_uncompressed) =
+                read_until_data_descriptor(&mut self.inner)?;
+            crc32 = real_crc32;
+            compressed_size = real_compressed;
+            uncompressed_size = real_uncompressed;
+            decode_by_method(method, Cursor::new(payload), real_uncompressed)?
+        } else {
+            decode_by_method(method, (&mut self.inner).take(compressed_size), uncompressed_size)?
+        };
+
+        Ok(Some(StreamingEntry {
+            name,
+            method,
+            crc32,
+            compressed_size,
+            uncompressed_size,
+            decoder,
+        }))
+    }
+}
+
+/// One entry read from a [`StreamingArchiveReader`]. Implements [`Read`]
+/// over the entry's decompressed contents.
+pub struct StreamingEntry<'a> {
+    pub name: String,
+    pub method: Method,
+    pub crc32: u32,
+    pub compressed_size: u64,
+    pub uncompressed_size: u64,
+    decoder: Box<dyn Read + 'a>,
+}
+
+impl<'a> Read for StreamingEntry<'a> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        self.decoder.read(buf)
+    }
+}
+
+/// Streams bytes from `r` looking for the data descriptor signature,
+/// returning everything read before it (the entry's compressed payload)
+/// plus the crc32/sizes that followed it. Buffers the payload in memory —
+/// there's no way to know where it ends without scanning forward for the
+/// signature, so unlike the fixed-size case this can't be a zero-copy
+/// pass-through. Reads one byte at a time, so nothing after the descriptor
+/// (the next entry's local file header) is taken from `r`.
+///
+/// The signature's 4 bytes aren't reserved — compressed data can contain
+/// the same bytes by coincidence — so a candidate match is only accepted
+/// once its descriptor's `compressed_size` field agrees with how many
+/// bytes actually precede it; otherwise the scan continues past it.
+fn read_until_data_descriptor<R: Read>(r: &mut R) -> Result<(Vec<u8>, u32, u64, u64)> {
+    let mut buf = Vec::new();
+    let mut chunk = [0u8; 1];
+    // Everything before this offset has already been scanned (and, if it
+    // contained a candidate signature, rejected) and needn't be looked at
+    // again; kept 3 bytes short of `buf.len()` so a signature split across
+    // two reads still gets found once the rest of it arrives.
+    let mut scanned_to = 0usize;
+
+    loop {
+        let n = r.read(&mut chunk)?;
+        if n == 0 {
+            return Err(Error::Unsupported(
+                "stream ended before data descriptor".into(),
+            ));
+        }
+        buf.extend_from_slice(&chunk[..n]);
+
+        // Only rewind 3 bytes once per newly-arrived chunk, to catch a
+        // signature split across this read and the previous one. Within the
+        // inner loop below, `search_from` instead advances past each
+        // rejected candidate without rewinding — that data was already
+        // fully buffered, so there's no boundary left to straddle, and
+        // rewinding on every rejection would keep re-finding the same
+        // candidate forever.
+        let mut search_from = scanned_to.saturating_sub(3);
+
+        loop {
+            let pos = match find_signature(&buf[search_from..]) {
+                Some(p) => search_from + p,
+                None => {
+                    scanned_to = buf.len().saturating_sub(3);
+                    break;
+                }
+            };
+            if buf.len() < pos + 16 {
+                // Found a candidate, but not enough trailing bytes yet to
+                // read the rest of the descriptor; retry this same
+                // candidate once more data has arrived.
+                scanned_to = pos;
+                break;
+            }
+
+            let descriptor = &buf[pos + 4..pos + 16];
+            let crc32 = u32::from_le_bytes([
+                descriptor[0],
+                descriptor[1],
+                descriptor[2],
+                descriptor[3],
+            ]);
+            let compressed_size = u32::from_le_bytes([
+                descriptor[4],
+                descriptor[5],
+                descriptor[6],
+                descriptor[7],
+            ]) as u64;
+            let uncompressed_size = u32::from_le_bytes([
+                descriptor[8],
+                descriptor[9],
+                descriptor[10],
+                descriptor[11],
+            ]) as u64;
+
+            if compressed_size == pos as u64 {
+                let payload = buf[..pos].to_vec();
+                return Ok((payload, crc32, compressed_size, uncompressed_size));
+            }
+            scanned_to = pos + 1;
+            search_from = scanned_to;
+        }
+    }
+}
+
+fn find_signature(haystack: &[u8]) -> Option<usize> {
+    let needle = DATA_DESCRIPTOR_SIG.to_le_bytes();
+    haystack.windows(4).position(|w| w == needle)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Builds a minimal local file header (Store method, data-descriptor
+    /// flag set, no extra field) followed by `payload` and a trailing data
+    /// descriptor record, as [`StreamingArchiveReader`] expects to find on
+    /// the wire.
+    fn build_entry(name: &str, payload: &[u8]) -> Vec<u8> {
+        let name_bytes = name.as_bytes();
+        let crc32 = crc32fast::hash(payload);
+
+        let mut out = Vec::new();
+        out.extend_from_slice(&LOCAL_FILE_HEADER_SIG.to_le_bytes());
+        out.extend_from_slice(&Version { host: 0, spec: 20 }.to_u16().to_le_bytes());
+        out.extend_from_slice(&FLAG_DATA_DESCRIPTOR.to_le_bytes());
+        out.extend_from_slice(&Method::Store.to_u16().to_le_bytes());
+        out.extend_from_slice(&0u16.to_le_bytes()); // mod time
+        out.extend_from_slice(&0u16.to_le_bytes()); // mod date
+        out.extend_from_slice(&0u32.to_le_bytes()); // crc32 (unknown, deferred)
+        out.extend_from_slice(&0u32.to_le_bytes()); // compressed size (unknown, deferred)
+        out.extend_from_slice(&0u32.to_le_bytes()); // uncompressed size (unknown, deferred)
+        out.extend_from_slice(&(name_bytes.len() as u16).to_le_bytes());
+        out.extend_from_slice(&0u16.to_le_bytes()); // extra field length
+        out.extend_from_slice(name_bytes);
+        out.extend_from_slice(payload);
+        out.extend_from_slice(&DATA_DESCRIPTOR_SIG.to_le_bytes());
+        out.extend_from_slice(&crc32.to_le_bytes());
+        out.extend_from_slice(&(payload.len() as u32).to_le_bytes());
+        out.extend_from_slice(&(payload.len() as u32).to_le_bytes());
+        out
+    }
+
+    /// The data descriptor signature isn't reserved, so compressed payload
+    /// bytes can contain the same 4 bytes by coincidence partway through an
+    /// entry. A naive "stop at the first signature match" scan would cut the
+    /// payload short there and misparse the rest as a descriptor; the real
+    /// descriptor must be found instead by verifying the candidate's
+    /// `compressed_size` field against how many bytes actually precede it.
+    #[test]
+    fn next_entry_skips_false_positive_signature_in_payload() {
+        let mut payload = Vec::new();
+        payload.extend_from_slice(b"hello-");
+        payload.extend_from_slice(&DATA_DESCRIPTOR_SIG.to_le_bytes());
+        payload.extend_from_slice(&[0u8; 12]); // looks like a descriptor body, but isn't one
+        payload.extend_from_slice(b"-world");
+
+        let bytes = build_entry("tricky.bin", &payload);
+        let mut reader = StreamingArchiveReader::new(Cursor::new(bytes));
+
+        let mut entry = reader.next_entry().unwrap().unwrap();
+        assert_eq!(entry.name, "tricky.bin");
+
+        let mut contents = Vec::new();
+        entry.read_to_end(&mut contents).unwrap();
+        assert_eq!(contents, payload);
+        assert_eq!(entry.compressed_size, payload.len() as u64);
+        assert_eq!(entry.uncompressed_size, payload.len() as u64);
+        assert_eq!(entry.crc32, crc32fast::hash(&payload));
+    }
+}
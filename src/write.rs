@@ -0,0 +1,548 @@
+//! Streaming ZIP writer: the mirror image of [`crate::reader`].
+
+use crate::error::Result;
+use crate::format::{
+    Method, Mode, Version, CENTRAL_DIRECTORY_ENTRY_SIG, EOCD64_LOCATOR_SIG, EOCD64_SIG, EOCD_SIG,
+    LOCAL_FILE_HEADER_SIG, UNIX_EXTRA_ID, ZIP64_EXTRA_ID, ZIP64_THRESHOLD,
+};
+use std::io::{Seek, SeekFrom, Write};
+
+/// Per-entry knobs the caller can set before handing data to [`ArchiveWriter::start_entry`].
+#[derive(Debug, Clone, Default)]
+pub struct EntryOptions {
+    pub mode: Mode,
+    pub uid: Option<u32>,
+    pub gid: Option<u32>,
+    pub modified: Option<u64>,
+    pub method: Method,
+}
+
+impl EntryOptions {
+    pub fn new(mode: Mode, method: Method) -> Self {
+        EntryOptions {
+            mode,
+            method,
+            ..Default::default()
+        }
+    }
+}
+
+struct PendingEntry {
+    name: String,
+    options: EntryOptions,
+    header_offset: u64,
+    crc32: u32,
+    compressed_size: u64,
+    uncompressed_size: u64,
+}
+
+/// Streams entries into a `Write + Seek` sink, then finalizes the central
+/// directory and end-of-central-directory record on [`ArchiveWriter::finish`].
+pub struct ArchiveWriter<W> {
+    sink: W,
+    offset: u64,
+    entries: Vec<PendingEntry>,
+}
+
+impl<W: Write + Seek> ArchiveWriter<W> {
+    pub fn new(sink: W) -> Self {
+        ArchiveWriter {
+            sink,
+            offset: 0,
+            entries: Vec::new(),
+        }
+    }
+
+    /// Starts a new entry, writing its local file header with zeroed
+    /// size/crc fields and a reserved (all-zero) ZIP64 extra field, both
+    /// patched in by [`EntryWriter::finish`] once the payload has actually
+    /// been streamed through. Reserving the extra field unconditionally
+    /// fixes the payload's offset up front, so the entry's bytes can be
+    /// written straight to `sink` as they arrive instead of being buffered
+    /// in full to first learn whether ZIP64 promotion is needed.
+    pub fn start_entry(&mut self, name: &str, options: EntryOptions) -> Result<EntryWriter<'_, W>> {
+        let header_offset = self.offset;
+        let name_bytes = name.as_bytes();
+
+        if !matches!(options.method, Method::Store | Method::Deflate) {
+            return Err(crate::error::Error::Unsupported(format!(
+                "writing with method {:?}",
+                options.method
+            )));
+        }
+
+        let (mod_date, mod_time) = match options.modified {
+            Some(ts) => unix_time_to_dos_datetime(ts),
+            None => (0, 0),
+        };
+        let unix_extra = build_unix_extra(options.uid, options.gid);
+
+        let mut header = Vec::with_capacity(50 + name_bytes.len() + unix_extra.len());
+        header.extend_from_slice(&LOCAL_FILE_HEADER_SIG.to_le_bytes());
+        header.extend_from_slice(&Version { host: 3, spec: 45 }.to_u16().to_le_bytes());
+        header.extend_from_slice(&0u16.to_le_bytes()); // general purpose flags
+        header.extend_from_slice(&options.method.to_u16().to_le_bytes());
+        header.extend_from_slice(&mod_time.to_le_bytes());
+        header.extend_from_slice(&mod_date.to_le_bytes());
+        header.extend_from_slice(&0u32.to_le_bytes()); // crc32 (patched later)
+        header.extend_from_slice(&0u32.to_le_bytes()); // compressed size (patched later)
+        header.extend_from_slice(&0u32.to_le_bytes()); // uncompressed size (patched later)
+        header.extend_from_slice(&(name_bytes.len() as u16).to_le_bytes());
+        header.extend_from_slice(&((20 + unix_extra.len()) as u16).to_le_bytes()); // extra field length: reserved ZIP64 record, plus Unix uid/gid if set
+        header.extend_from_slice(name_bytes);
+        header.extend_from_slice(&ZIP64_EXTRA_ID.to_le_bytes());
+        header.extend_from_slice(&16u16.to_le_bytes());
+        header.extend_from_slice(&0u64.to_le_bytes()); // uncompressed size (patched later)
+        header.extend_from_slice(&0u64.to_le_bytes()); // compressed size (patched later)
+        header.extend_from_slice(&unix_extra);
+
+        self.sink.write_all(&header)?;
+        self.offset += header.len() as u64;
+        let payload_offset = self.offset;
+
+        let counting = CountingSink {
+            sink: &mut self.sink,
+            written: 0,
+        };
+        let compressor = match options.method {
+            Method::Store => Compressor::Store(counting),
+            Method::Deflate => Compressor::Deflate(flate2::write::DeflateEncoder::new(
+                counting,
+                flate2::Compression::default(),
+            )),
+            _ => unreachable!("checked above"),
+        };
+
+        Ok(EntryWriter {
+            name: name.to_string(),
+            options,
+            header_offset,
+            payload_offset,
+            crc32: crc32fast::Hasher::new(),
+            uncompressed_size: 0,
+            compressor,
+            running_offset: &mut self.offset,
+            entries: &mut self.entries,
+        })
+    }
+
+    /// Test-only equivalent of [`EntryWriter::finish`] that patches a
+    /// fabricated [`PendingEntry`] directly, without actually streaming
+    /// `compressed_size`/`uncompressed_size` bytes through the sink. Used to
+    /// exercise ZIP64 promotion without driving a real multi-gigabyte write;
+    /// leaves `self.offset` at the (never-written) payload's start, since
+    /// the central directory only needs the claimed sizes, not real bytes.
+    #[cfg(test)]
+    fn finish_entry(&mut self, pending: PendingEntry) -> Result<()> {
+        patch_local_header(
+            &mut self.sink,
+            pending.header_offset,
+            pending.name.len(),
+            pending.crc32,
+            pending.compressed_size,
+            pending.uncompressed_size,
+        )?;
+        self.sink.seek(SeekFrom::Start(self.offset))?;
+        self.entries.push(pending);
+        Ok(())
+    }
+
+    /// Writes the central directory and the (ZIP64, if needed)
+    /// end-of-central-directory record, consuming the writer.
+    pub fn finish(mut self) -> Result<W> {
+        let cd_offset = self.offset;
+        let needs_zip64 = cd_offset >= ZIP64_THRESHOLD
+            || self.entries.len() > u16::MAX as usize
+            || self
+                .entries
+                .iter()
+                .any(|e| e.compressed_size >= ZIP64_THRESHOLD || e.uncompressed_size >= ZIP64_THRESHOLD);
+
+        for entry in &self.entries {
+            let name_bytes = entry.name.as_bytes();
+
+            // Per APPNOTE 4.5.3, the ZIP64 extra field carries only the
+            // fields that actually overflow 32 bits, in this fixed order:
+            // uncompressed size, compressed size, then header offset.
+            let mut zip64_data = Vec::new();
+            if entry.uncompressed_size >= ZIP64_THRESHOLD {
+                zip64_data.extend_from_slice(&entry.uncompressed_size.to_le_bytes());
+            }
+            if entry.compressed_size >= ZIP64_THRESHOLD {
+                zip64_data.extend_from_slice(&entry.compressed_size.to_le_bytes());
+            }
+            if entry.header_offset >= ZIP64_THRESHOLD {
+                zip64_data.extend_from_slice(&entry.header_offset.to_le_bytes());
+            }
+            let mut extra = Vec::new();
+            if !zip64_data.is_empty() {
+                extra.extend_from_slice(&ZIP64_EXTRA_ID.to_le_bytes());
+                extra.extend_from_slice(&(zip64_data.len() as u16).to_le_bytes());
+                extra.extend_from_slice(&zip64_data);
+            }
+            extra.extend_from_slice(&build_unix_extra(entry.options.uid, entry.options.gid));
+
+            let (mod_date, mod_time) = match entry.options.modified {
+                Some(ts) => unix_time_to_dos_datetime(ts),
+                None => (0, 0),
+            };
+
+            let mut rec = Vec::with_capacity(46 + name_bytes.len() + extra.len());
+            rec.extend_from_slice(&CENTRAL_DIRECTORY_ENTRY_SIG.to_le_bytes());
+            rec.extend_from_slice(&Version { host: 3, spec: 20 }.to_u16().to_le_bytes());
+            rec.extend_from_slice(&Version { host: 0, spec: 20 }.to_u16().to_le_bytes());
+            rec.extend_from_slice(&0u16.to_le_bytes()); // general purpose flags
+            rec.extend_from_slice(&entry.options.method.to_u16().to_le_bytes());
+            rec.extend_from_slice(&mod_time.to_le_bytes());
+            rec.extend_from_slice(&mod_date.to_le_bytes());
+            rec.extend_from_slice(&entry.crc32.to_le_bytes());
+            rec.extend_from_slice(&(entry.compressed_size.min(ZIP64_THRESHOLD) as u32).to_le_bytes());
+            rec.extend_from_slice(&(entry.uncompressed_size.min(ZIP64_THRESHOLD) as u32).to_le_bytes());
+            rec.extend_from_slice(&(name_bytes.len() as u16).to_le_bytes());
+            rec.extend_from_slice(&(extra.len() as u16).to_le_bytes());
+            rec.extend_from_slice(&0u16.to_le_bytes()); // comment length
+            rec.extend_from_slice(&0u16.to_le_bytes()); // disk number start
+            rec.extend_from_slice(&0u16.to_le_bytes()); // internal attributes
+            rec.extend_from_slice(&entry.options.mode.to_external_attrs().to_le_bytes());
+            rec.extend_from_slice(&(entry.header_offset.min(ZIP64_THRESHOLD) as u32).to_le_bytes());
+            rec.extend_from_slice(name_bytes);
+            rec.extend_from_slice(&extra);
+
+            self.sink.write_all(&rec)?;
+            self.offset += rec.len() as u64;
+        }
+
+        let cd_size = self.offset - cd_offset;
+
+        if needs_zip64 {
+            let eocd64_offset = self.offset;
+            let mut eocd64 = Vec::with_capacity(56);
+            eocd64.extend_from_slice(&EOCD64_SIG.to_le_bytes());
+            eocd64.extend_from_slice(&44u64.to_le_bytes()); // size of this record - 12
+            eocd64.extend_from_slice(&Version { host: 3, spec: 45 }.to_u16().to_le_bytes());
+            eocd64.extend_from_slice(&Version { host: 0, spec: 45 }.to_u16().to_le_bytes());
+            eocd64.extend_from_slice(&0u32.to_le_bytes()); // disk number
+            eocd64.extend_from_slice(&0u32.to_le_bytes()); // disk with central dir
+            eocd64.extend_from_slice(&(self.entries.len() as u64).to_le_bytes());
+            eocd64.extend_from_slice(&(self.entries.len() as u64).to_le_bytes());
+            eocd64.extend_from_slice(&cd_size.to_le_bytes());
+            eocd64.extend_from_slice(&cd_offset.to_le_bytes());
+            self.sink.write_all(&eocd64)?;
+
+            let mut locator = Vec::with_capacity(20);
+            locator.extend_from_slice(&EOCD64_LOCATOR_SIG.to_le_bytes());
+            locator.extend_from_slice(&0u32.to_le_bytes()); // disk with eocd64
+            locator.extend_from_slice(&eocd64_offset.to_le_bytes());
+            locator.extend_from_slice(&1u32.to_le_bytes()); // total number of disks
+            self.sink.write_all(&locator)?;
+        }
+
+        let entry_count = self.entries.len().min(u16::MAX as usize) as u16;
+        let mut eocd = Vec::with_capacity(22);
+        eocd.extend_from_slice(&EOCD_SIG.to_le_bytes());
+        eocd.extend_from_slice(&0u16.to_le_bytes()); // disk number
+        eocd.extend_from_slice(&0u16.to_le_bytes()); // disk with central dir
+        eocd.extend_from_slice(&entry_count.to_le_bytes());
+        eocd.extend_from_slice(&entry_count.to_le_bytes());
+        eocd.extend_from_slice(&(cd_size.min(ZIP64_THRESHOLD) as u32).to_le_bytes());
+        eocd.extend_from_slice(&(cd_offset.min(ZIP64_THRESHOLD) as u32).to_le_bytes());
+        eocd.extend_from_slice(&0u16.to_le_bytes()); // comment length
+        self.sink.write_all(&eocd)?;
+
+        Ok(self.sink)
+    }
+}
+
+/// Wraps the archive's sink to count the compressed bytes actually written
+/// for the entry in progress, without buffering them.
+struct CountingSink<'a, W> {
+    sink: &'a mut W,
+    written: u64,
+}
+
+impl<'a, W: Write> Write for CountingSink<'a, W> {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        let n = self.sink.write(buf)?;
+        self.written += n as u64;
+        Ok(n)
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        self.sink.flush()
+    }
+}
+
+enum Compressor<'a, W: Write> {
+    Store(CountingSink<'a, W>),
+    Deflate(flate2::write::DeflateEncoder<CountingSink<'a, W>>),
+}
+
+/// Accepts uncompressed bytes for the entry currently being written,
+/// streaming them through [`EntryOptions::method`]'s compressor straight to
+/// the archive's sink while tracking the CRC-32 the central directory will
+/// need. The local file header (and its reserved ZIP64 extra field) is
+/// patched in place once [`EntryWriter::finish`] learns the real sizes.
+pub struct EntryWriter<'a, W: Write + Seek> {
+    name: String,
+    options: EntryOptions,
+    header_offset: u64,
+    payload_offset: u64,
+    crc32: crc32fast::Hasher,
+    uncompressed_size: u64,
+    compressor: Compressor<'a, W>,
+    running_offset: &'a mut u64,
+    entries: &'a mut Vec<PendingEntry>,
+}
+
+impl<'a, W: Write + Seek> EntryWriter<'a, W> {
+    pub fn write_all(&mut self, buf: &[u8]) -> Result<()> {
+        self.crc32.update(buf);
+        self.uncompressed_size += buf.len() as u64;
+        match &mut self.compressor {
+            Compressor::Store(sink) => sink.write_all(buf)?,
+            Compressor::Deflate(encoder) => encoder.write_all(buf)?,
+        }
+        Ok(())
+    }
+
+    /// Flushes the compressor, patches the crc32/sizes (and the reserved
+    /// ZIP64 extra field) back into the local file header, and records this
+    /// entry in the archive's pending central directory.
+    pub fn finish(self) -> Result<()> {
+        let counting = match self.compressor {
+            Compressor::Store(sink) => sink,
+            Compressor::Deflate(encoder) => encoder.finish()?,
+        };
+        let compressed_size = counting.written;
+        let sink = counting.sink;
+        let crc32 = self.crc32.finalize();
+
+        patch_local_header(
+            sink,
+            self.header_offset,
+            self.name.len(),
+            crc32,
+            compressed_size,
+            self.uncompressed_size,
+        )?;
+
+        *self.running_offset = self.payload_offset + compressed_size;
+        sink.seek(SeekFrom::Start(*self.running_offset))?;
+
+        self.entries.push(PendingEntry {
+            name: self.name,
+            compressed_size,
+            uncompressed_size: self.uncompressed_size,
+            header_offset: self.header_offset,
+            crc32,
+            options: self.options,
+        });
+
+        Ok(())
+    }
+}
+
+/// Patches a local file header already written at `header_offset`: the
+/// crc32/size fields (using the ZIP64 sentinel for both sizes if either
+/// reaches [`ZIP64_THRESHOLD`]), and the real sizes into the reserved ZIP64 extra field that
+/// [`ArchiveWriter::start_entry`] wrote right after the name. Leaves the
+/// sink positioned wherever it lands after the last write, so callers must
+/// seek back to resume appending afterward.
+fn patch_local_header<W: Write + Seek>(
+    sink: &mut W,
+    header_offset: u64,
+    name_len: usize,
+    crc32: u32,
+    compressed_size: u64,
+    uncompressed_size: u64,
+) -> Result<()> {
+    let needs_zip64 = compressed_size >= ZIP64_THRESHOLD || uncompressed_size >= ZIP64_THRESHOLD;
+
+    sink.seek(SeekFrom::Start(header_offset + 14))?;
+    sink.write_all(&crc32.to_le_bytes())?;
+    if needs_zip64 {
+        sink.write_all(&(ZIP64_THRESHOLD as u32).to_le_bytes())?;
+        sink.write_all(&(ZIP64_THRESHOLD as u32).to_le_bytes())?;
+    } else {
+        sink.write_all(&(compressed_size as u32).to_le_bytes())?;
+        sink.write_all(&(uncompressed_size as u32).to_le_bytes())?;
+    }
+
+    let extra_offset = header_offset + 30 + name_len as u64;
+    sink.seek(SeekFrom::Start(extra_offset + 4))?;
+    sink.write_all(&uncompressed_size.to_le_bytes())?;
+    sink.write_all(&compressed_size.to_le_bytes())?;
+
+    Ok(())
+}
+
+/// Inverse of [`crate::reader`]'s `dos_datetime_to_unix`: encodes a Unix
+/// timestamp as DOS date/time fields, clamping to the DOS epoch
+/// (1980-01-01, the earliest representable date) if `timestamp` predates it.
+fn unix_time_to_dos_datetime(timestamp: u64) -> (u16, u16) {
+    const DOS_EPOCH: u64 = 315_532_800; // 1980-01-01T00:00:00Z
+    let timestamp = timestamp.max(DOS_EPOCH);
+    let days = (timestamp / 86400) as i64;
+    let seconds_of_day = timestamp % 86400;
+
+    let (year, month, day) = civil_from_days(days);
+    let hour = seconds_of_day / 3600;
+    let minute = (seconds_of_day % 3600) / 60;
+    let second = seconds_of_day % 60;
+
+    let date = (((year - 1980).min(127) as u16) << 9) | ((month as u16) << 5) | day as u16;
+    let time = ((hour as u16) << 11) | ((minute as u16) << 5) | (second / 2) as u16;
+    (date, time)
+}
+
+/// Inverse of [`crate::reader`]'s `days_from_civil`: converts a day count
+/// relative to the Unix epoch back into a (year, month, day) calendar date.
+/// Based on Howard Hinnant's `civil_from_days` algorithm.
+fn civil_from_days(days: i64) -> (i64, i64, i64) {
+    let z = days + 719468;
+    let era = if z >= 0 { z } else { z - 146096 } / 146097;
+    let doe = z - era * 146097;
+    let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146096) / 365;
+    let y = yoe + era * 400;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    (if month <= 2 { y + 1 } else { y }, month, day)
+}
+
+/// Builds an Info-ZIP "new Unix" extra field (the same format
+/// [`crate::reader`] parses) carrying `uid`/`gid` as 4-byte little-endian
+/// integers, or an empty record if neither is set.
+fn build_unix_extra(uid: Option<u32>, gid: Option<u32>) -> Vec<u8> {
+    if uid.is_none() && gid.is_none() {
+        return Vec::new();
+    }
+
+    let mut data = vec![1u8]; // version
+    data.push(4u8); // uid size
+    data.extend_from_slice(&uid.unwrap_or(0).to_le_bytes());
+    data.push(4u8); // gid size
+    data.extend_from_slice(&gid.unwrap_or(0).to_le_bytes());
+
+    let mut extra = Vec::with_capacity(4 + data.len());
+    extra.extend_from_slice(&UNIX_EXTRA_ID.to_le_bytes());
+    extra.extend_from_slice(&(data.len() as u16).to_le_bytes());
+    extra.extend_from_slice(&data);
+    extra
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::prelude::*;
+    use std::io::Cursor;
+
+    /// Fabricates an entry whose sizes are already past [`ZIP64_THRESHOLD`]
+    /// (skipping an actual multi-gigabyte write) to exercise ZIP64
+    /// promotion end to end: the local-header extra field, the central
+    /// directory's per-field extra data, and the EOCD64/locator record.
+    #[test]
+    fn zip64_promotion_round_trips() {
+        let mut writer = ArchiveWriter::new(Cursor::new(Vec::new()));
+
+        let options = EntryOptions::new(Mode(0o644), Method::Store);
+        let entry_writer = writer.start_entry("huge.bin", options.clone()).unwrap();
+        let header_offset = entry_writer.header_offset;
+        drop(entry_writer);
+
+        writer
+            .finish_entry(PendingEntry {
+                name: "huge.bin".to_string(),
+                options,
+                header_offset,
+                crc32: 0xDEAD_BEEF,
+                compressed_size: ZIP64_THRESHOLD + 1,
+                uncompressed_size: ZIP64_THRESHOLD + 1,
+            })
+            .unwrap();
+
+        let cursor = writer.finish().unwrap();
+
+        let tmp = tempfile::NamedTempFile::new().unwrap();
+        std::fs::write(tmp.path(), cursor.into_inner()).unwrap();
+        let archive = std::fs::File::open(tmp.path()).unwrap().read_zip().unwrap();
+
+        let entry = archive.entries().find(|e| e.name() == "huge.bin").unwrap();
+        assert_eq!(entry.compressed_size, ZIP64_THRESHOLD + 1);
+        assert_eq!(entry.uncompressed_size, ZIP64_THRESHOLD + 1);
+    }
+
+    /// `EntryOptions::uid`/`gid`/`modified` must actually reach the archive
+    /// (as an Info-ZIP Unix extra field and a DOS date/time, respectively),
+    /// not just sit on the struct unused.
+    #[test]
+    fn entry_options_uid_gid_modified_round_trip() {
+        let mut writer = ArchiveWriter::new(Cursor::new(Vec::new()));
+
+        let mut options = EntryOptions::new(Mode(0o644), Method::Store);
+        options.uid = Some(1000);
+        options.gid = Some(1001);
+        options.modified = Some(1_700_000_000); // 2023-11-14T22:13:20Z
+
+        let mut entry_writer = writer.start_entry("owned.txt", options).unwrap();
+        entry_writer.write_all(b"hello").unwrap();
+        entry_writer.finish().unwrap();
+
+        let cursor = writer.finish().unwrap();
+
+        let tmp = tempfile::NamedTempFile::new().unwrap();
+        std::fs::write(tmp.path(), cursor.into_inner()).unwrap();
+        let archive = std::fs::File::open(tmp.path()).unwrap().read_zip().unwrap();
+
+        let entry = archive.entries().find(|e| e.name() == "owned.txt").unwrap();
+        assert_eq!(entry.uid, Some(1000));
+        assert_eq!(entry.gid, Some(1001));
+        // DOS date/time only has 2-second resolution, so allow for rounding.
+        assert!((entry.modified_timestamp as i64 - 1_700_000_000).abs() <= 1);
+    }
+
+    /// A size of exactly [`ZIP64_THRESHOLD`] (`0xFFFF_FFFF`) is itself the
+    /// reserved ZIP64 escape value, not a representable 32-bit size, so it
+    /// must promote to ZIP64 too — not just sizes strictly greater than the
+    /// threshold. Checked against the raw central directory record rather
+    /// than through `read_zip`: at exactly the threshold the real size and
+    /// the sentinel are the same bit pattern, so a reader that (like this
+    /// crate's) tolerates a missing extra field would report the right size
+    /// by coincidence even without one actually being written.
+    #[test]
+    fn zip64_promotion_triggers_at_exact_threshold() {
+        let mut writer = ArchiveWriter::new(Cursor::new(Vec::new()));
+
+        let options = EntryOptions::new(Mode(0o644), Method::Store);
+        let entry_writer = writer.start_entry("exact.bin", options.clone()).unwrap();
+        let header_offset = entry_writer.header_offset;
+        let cd_offset = entry_writer.payload_offset;
+        drop(entry_writer);
+
+        writer
+            .finish_entry(PendingEntry {
+                name: "exact.bin".to_string(),
+                options,
+                header_offset,
+                crc32: 0xDEAD_BEEF,
+                compressed_size: ZIP64_THRESHOLD,
+                uncompressed_size: ZIP64_THRESHOLD,
+            })
+            .unwrap();
+
+        let bytes = writer.finish().unwrap().into_inner();
+
+        // Central directory record layout (APPNOTE 4.3.12): extra field
+        // length is the u16 at record offset 30.
+        let extra_len = u16::from_le_bytes(
+            bytes[cd_offset as usize + 30..cd_offset as usize + 32]
+                .try_into()
+                .unwrap(),
+        );
+        assert!(
+            extra_len > 0,
+            "central directory record must carry a ZIP64 extra field when a size is exactly ZIP64_THRESHOLD"
+        );
+    }
+}